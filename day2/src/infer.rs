@@ -0,0 +1,196 @@
+use std::error::Error;
+use std::fmt;
+
+use crate::{columns, score, Game, Hand, Part, HANDS};
+
+const PERMUTATIONS: [[usize; 3]; 6] = [
+    [0, 1, 2],
+    [0, 2, 1],
+    [1, 0, 2],
+    [1, 2, 0],
+    [2, 0, 1],
+    [2, 1, 0],
+];
+
+const GAMES: [Game; 3] = [Game::Lose, Game::Draw, Game::Win];
+
+/// A reading of the second column of the strategy guide, mapping `X`, `Y`
+/// and `Z` either to the shape you play or to how the round must end.
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub enum Interpretation {
+    Shapes([Hand; 3]),
+    Outcomes([Game; 3]),
+}
+
+/// What is known about the score the guide produced.
+pub enum Observation<'a> {
+    Total(u32),
+    Rounds(&'a [u32]),
+}
+
+fn column_index(string: &str) -> Result<usize, &'static str> {
+    match string {
+        "X" => Ok(0),
+        "Y" => Ok(1),
+        "Z" => Ok(2),
+        _ => Err("InvalidResponse"),
+    }
+}
+
+impl From<&Part> for Interpretation {
+    fn from(part: &Part) -> Self {
+        match *part {
+            Part::Part1 => Interpretation::Shapes(HANDS),
+            Part::Part2 => Interpretation::Outcomes(GAMES),
+        }
+    }
+}
+
+impl Interpretation {
+    /// Every shape cipher and every outcome cipher.
+    pub fn all() -> Vec<Interpretation> {
+        let shapes = PERMUTATIONS
+            .iter()
+            .map(|p| Interpretation::Shapes(p.map(|i| HANDS[i])));
+        let outcomes = PERMUTATIONS
+            .iter()
+            .map(|p| Interpretation::Outcomes(p.map(|i| GAMES[i])));
+
+        shapes.chain(outcomes).collect()
+    }
+
+    /// Scores a line as this interpretation reads it, leaving the puzzle's
+    /// own two readings to `crate::score_line`.
+    pub fn score_line(&self, line: &str) -> Result<u32, Box<dyn Error>> {
+        if let Some(part) = self.part() {
+            return crate::score_line(line, &part);
+        }
+
+        let (first, second) = columns(line)?;
+        let theirs = Hand::try_from(first).map_err(|_| "InvalidOpponentShape")?;
+        let column = column_index(second)?;
+
+        match self {
+            Interpretation::Shapes(shapes) => Ok(score(&theirs, &shapes[column])),
+            Interpretation::Outcomes(outcomes) => {
+                let yours = theirs.opponent_from_result(&!outcomes[column]);
+                Ok(score(&theirs, yours))
+            }
+        }
+    }
+
+    fn part(&self) -> Option<Part> {
+        [Part::Part1, Part::Part2]
+            .into_iter()
+            .find(|part| Interpretation::from(part) == *self)
+    }
+}
+
+impl fmt::Display for Interpretation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let names: Vec<String> = match self {
+            Interpretation::Shapes(shapes) => shapes.iter().map(|h| format!("{:?}", h)).collect(),
+            Interpretation::Outcomes(outcomes) => {
+                outcomes.iter().map(|g| format!("{:?}", g)).collect()
+            }
+        };
+        write!(f, "X={} Y={} Z={}", names[0], names[1], names[2])?;
+
+        match self.part() {
+            Some(Part::Part1) => write!(f, " (part 1)"),
+            Some(Part::Part2) => write!(f, " (part 2)"),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Returns every interpretation of the guide that reproduces the observed
+/// scores.
+pub fn consistent(
+    lines: &[&str],
+    observed: &Observation,
+) -> Result<Vec<Interpretation>, Box<dyn Error>> {
+    if let Observation::Rounds(rounds) = observed {
+        if rounds.len() != lines.len() {
+            return Err("RoundCountMismatch".into());
+        }
+    }
+
+    let mut result = Vec::new();
+    for interpretation in Interpretation::all() {
+        let scores = lines
+            .iter()
+            .map(|l| interpretation.score_line(l))
+            .collect::<Result<Vec<u32>, _>>()?;
+
+        let matches = match observed {
+            Observation::Total(total) => scores.iter().sum::<u32>() == *total,
+            Observation::Rounds(rounds) => scores == *rounds,
+        };
+        if matches {
+            result.push(interpretation);
+        }
+    }
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{score_line, score_lines};
+
+    #[test]
+    fn test_all_interpretations() {
+        let all = Interpretation::all();
+        assert_eq!(all.len(), 12);
+        assert!(all.contains(&Interpretation::from(&Part::Part1)));
+        assert!(all.contains(&Interpretation::from(&Part::Part2)));
+    }
+
+    #[test]
+    fn test_consistent_with_total() -> Result<(), Box<dyn Error>> {
+        let input = include_str!("../data/input.txt");
+        let lines: Vec<&str> = input.lines().collect();
+
+        for part in [Part::Part1, Part::Part2] {
+            let total = score_lines(&lines, &part)?;
+            let found = consistent(&lines, &Observation::Total(total))?;
+            assert!(found.contains(&Interpretation::from(&part)));
+        }
+
+        assert!(consistent(&lines, &Observation::Total(0))?.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_consistent_with_rounds() -> Result<(), Box<dyn Error>> {
+        let lines = ["A Y", "B X", "C Z"];
+
+        let found = consistent(&lines, &Observation::Rounds(&[8, 1, 6]))?;
+        assert_eq!(
+            found,
+            vec![
+                Interpretation::from(&Part::Part1),
+                Interpretation::Outcomes([Game::Lose, Game::Win, Game::Draw]),
+            ]
+        );
+
+        let found = consistent(&lines, &Observation::Rounds(&[4, 1, 7]))?;
+        assert_eq!(found, vec![Interpretation::from(&Part::Part2)]);
+
+        assert!(consistent(&lines, &Observation::Rounds(&[4, 1])).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_rejects_what_score_line_rejects() {
+        for line in ["A Y Z", "A", "", "D Y", "A Q"] {
+            for part in [Part::Part1, Part::Part2] {
+                assert!(score_line(line, &part).is_err());
+                assert!(Interpretation::from(&part).score_line(line).is_err());
+            }
+            assert!(consistent(&["A Y", line], &Observation::Total(8)).is_err());
+        }
+    }
+}
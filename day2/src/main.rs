@@ -50,9 +50,18 @@ If you still want to see it, you can get your puzzle input.
 
 
 */
+mod infer;
+mod nash;
+mod repl;
+mod table;
+mod validate;
+
 use std::convert::TryFrom;
+use std::env;
 use std::error::Error;
+use std::io;
 use std::ops::Not;
+use std::time::Instant;
 
 #[derive(PartialEq, Eq)]
 enum Part {
@@ -60,7 +69,7 @@ enum Part {
     Part2,
 }
 
-#[derive(Debug, Eq, PartialEq)]
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
 enum Game {
     Win,
     Lose,
@@ -92,13 +101,15 @@ impl Not for Game {
     }
 }
 
-#[derive(Debug, Eq, PartialEq)]
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
 enum Hand {
     Rock = 1,
     Paper = 2,
     Sizzors = 3,
 }
 
+const HANDS: [Hand; 3] = [Hand::Rock, Hand::Paper, Hand::Sizzors];
+
 impl TryFrom<&str> for Hand {
     type Error = &'static str;
 
@@ -165,33 +176,177 @@ fn score(theirs: &Hand, yours: &Hand) -> u32 {
     }
 }
 
-fn score_line(line: &str, part: &Part) -> Result<u32, Box<dyn Error>> {
+/// Splits a line into its two whitespace separated columns.
+fn columns(line: &str) -> Result<(&str, &str), &'static str> {
     let mut splits = line.split_whitespace();
-    let theirs = Hand::try_from(splits.next().unwrap())?;
+    let theirs = splits.next().ok_or("MissingColumn")?;
+    let yours = splits.next().ok_or("MissingColumn")?;
+
+    match splits.next() {
+        Some(_) => Err("TrailingColumn"),
+        None => Ok((theirs, yours)),
+    }
+}
+
+fn score_line(line: &str, part: &Part) -> Result<u32, Box<dyn Error>> {
+    let (first, second) = columns(line)?;
+    let theirs = Hand::try_from(first).map_err(|_| "InvalidOpponentShape")?;
 
     match *part {
         Part::Part1 => {
-            let yours = Hand::try_from(splits.next().unwrap())?;
+            let yours = Hand::try_from(second).map_err(|_| "InvalidResponse")?;
             Ok(score(&theirs, &yours))
         }
         Part::Part2 => {
-            let result = Game::try_from(splits.next().unwrap())?;
+            let result = Game::try_from(second).map_err(|_| "InvalidResponse")?;
             let yours = theirs.opponent_from_result(&(!result));
             Ok(score(&theirs, yours))
         }
     }
 }
 
-fn score_lines(lines: &[&str], part: &Part) -> u32 {
-    lines.iter().map(|l| score_line(l, part).unwrap()).sum()
+/// Sums the score of every line, stopping at the first one that cannot be
+/// scored.
+fn score_lines(lines: &[&str], part: &Part) -> Result<u32, validate::Diagnostic> {
+    lines
+        .iter()
+        .enumerate()
+        .map(|(i, line)| {
+            score_line(line, part).map_err(|e| validate::Diagnostic {
+                line_number: i + 1,
+                line: line.to_string(),
+                reason: e.to_string(),
+            })
+        })
+        .sum()
 }
 
-fn main() {
-    let input = include_str!("../data/input.txt");
+/// Steps a linear congruential generator, returning 31 random bits, so that
+/// simulations are reproducible without a dependency on `rand`.
+fn next_random(state: &mut u64) -> u32 {
+    *state = state
+        .wrapping_mul(6364136223846793005)
+        .wrapping_add(1442695040888963407);
+    (*state >> 33) as u32
+}
 
+fn print_equilibrium() -> Result<(), Box<dyn Error>> {
+    let eq = nash::solve(&nash::score_matrix())?;
+
+    println!("Game value {:.4}", eq.value);
+    for (i, hand) in HANDS.iter().enumerate() {
+        println!(
+            "{:?}: yours {:.4}, theirs {:.4}",
+            hand, eq.row_strategy[i], eq.column_strategy[i]
+        );
+    }
+
+    Ok(())
+}
+
+fn print_interpretations(lines: &[&str], scores: &[String]) -> Result<(), Box<dyn Error>> {
+    let scores = scores
+        .iter()
+        .map(|s| s.parse::<u32>())
+        .collect::<Result<Vec<u32>, _>>()?;
+
+    let observed = match scores.as_slice() {
+        [] => return Err("usage: infer TOTAL, or infer SCORE... with one score per round".into()),
+        [total] => infer::Observation::Total(*total),
+        rounds => infer::Observation::Rounds(rounds),
+    };
+
+    for interpretation in infer::consistent(lines, &observed)? {
+        println!("{}", interpretation);
+    }
+
+    Ok(())
+}
+
+fn print_diagnostics(lines: &[&str]) -> Result<(), Box<dyn Error>> {
+    let diagnostics = validate::validate_guide(lines);
+    for diagnostic in &diagnostics {
+        println!("{}", diagnostic);
+    }
+
+    match diagnostics.len() {
+        0 => Ok(()),
+        malformed => Err(format!("{} malformed lines", malformed).into()),
+    }
+}
+
+fn print_lenient_scores(lines: &[&str]) {
+    for (name, part) in [("Part 1", Part::Part1), ("Part 2", Part::Part2)] {
+        let result = validate::score_lines_lenient(lines, &part);
+        println!(
+            "{} answer {} (skipped {} lines)",
+            name,
+            result.score,
+            result.skipped.len()
+        );
+    }
+}
+
+fn benchmark(rounds: usize) -> Result<(), Box<dyn Error>> {
+    let guide = table::synthetic_guide(rounds, 2022);
+
+    let start = Instant::now();
+    let lines: Vec<&str> = guide.lines().collect();
+    let expected = (
+        score_lines(&lines, &Part::Part1)?,
+        score_lines(&lines, &Part::Part2)?,
+    );
+    let parsed = start.elapsed();
+
+    let start = Instant::now();
+    let actual = table::score_bytes(guide.as_bytes())?;
+    let tabled = start.elapsed();
+
+    if actual != expected {
+        return Err(format!("table scored {:?}, expected {:?}", actual, expected).into());
+    }
+
+    println!("{} rounds scored {:?}", rounds, actual);
+    println!("score_lines {:?}", parsed);
+    println!("score_bytes {:?}", tabled);
+
+    Ok(())
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    let input = include_str!("../data/input.txt");
     let lines: Vec<&str> = input.lines().collect();
-    println!("Part 1 answer {}", score_lines(&lines, &Part::Part1));
-    println!("Part 2 answer {}", score_lines(&lines, &Part::Part2));
+
+    match args.first().map(String::as_str) {
+        Some("nash") => print_equilibrium(),
+        Some("infer") => print_interpretations(&lines, &args[1..]),
+        Some("bench") => {
+            let rounds = match args.get(1) {
+                Some(n) => n.parse()?,
+                None => 10_000_000,
+            };
+            benchmark(rounds)
+        }
+        Some("play") => {
+            let mut strategy =
+                repl::Strategy::try_from(args.get(1).map_or("random", String::as_str))?;
+            let tally = repl::play(io::stdin().lock(), io::stdout(), &mut strategy)?;
+            println!("Final score: you {}, them {}", tally.yours, tally.theirs);
+            Ok(())
+        }
+        Some("validate") => print_diagnostics(&lines),
+        Some("lenient") => {
+            print_lenient_scores(&lines);
+            Ok(())
+        }
+        _ => {
+            println!("Part 1 answer {}", score_lines(&lines, &Part::Part1)?);
+            println!("Part 2 answer {}", score_lines(&lines, &Part::Part2)?);
+            Ok(())
+        }
+    }
 }
 
 #[cfg(test)]
@@ -275,10 +430,33 @@ pub mod tests {
     }
 
     #[test]
-    fn test_score_lines_from_file() {
+    fn test_print_interpretations_needs_scores() {
+        let lines = ["A Y", "B X", "C Z"];
+        let error = print_interpretations(&lines, &[]).unwrap_err();
+        assert!(error.to_string().starts_with("usage: infer"));
+        assert!(print_interpretations(&lines, &["15".to_string()]).is_ok());
+    }
+
+    #[test]
+    fn test_print_diagnostics_counts_lines_once() {
+        let error = print_diagnostics(&["A Y", "A", "C Z"]).unwrap_err();
+        assert_eq!(error.to_string(), "1 malformed lines");
+        assert!(print_diagnostics(&["A Y", "C Z"]).is_ok());
+    }
+
+    #[test]
+    fn test_score_lines_from_file() -> Result<(), Box<dyn Error>> {
         let input = include_str!("../data/input_test.txt");
         let lines: Vec<&str> = input.lines().collect();
-        assert_eq!(score_lines(&lines, &Part::Part1), 15);
-        assert_eq!(score_lines(&lines, &Part::Part2), 12);
+        assert_eq!(score_lines(&lines, &Part::Part1)?, 15);
+        assert_eq!(score_lines(&lines, &Part::Part2)?, 12);
+        Ok(())
+    }
+
+    #[test]
+    fn test_score_lines_reports_malformed_line() {
+        let error = score_lines(&["A Y", "D Q", "C Z"], &Part::Part1).unwrap_err();
+        assert_eq!(error.line_number, 2);
+        assert_eq!(error.to_string(), "line 2: InvalidOpponentShape (\"D Q\")");
     }
 }
@@ -0,0 +1,222 @@
+use crate::{score, Hand, HANDS};
+
+const EPSILON: f64 = 1e-9;
+
+/// Optimal mixed strategies for both players of a zero-sum matrix game,
+/// where the row player receives the payoff and the column player pays it.
+#[derive(Debug)]
+pub struct Equilibrium {
+    pub row_strategy: Vec<f64>,
+    pub column_strategy: Vec<f64>,
+    pub value: f64,
+}
+
+/// Builds a payoff matrix from a rules table, with one row per shape you
+/// can play and one column per shape your opponent can play.
+pub fn payoff_matrix<F>(hands: &[Hand], rules: F) -> Vec<Vec<f64>>
+where
+    F: Fn(&Hand, &Hand) -> u32,
+{
+    hands
+        .iter()
+        .map(|yours| {
+            hands
+                .iter()
+                .map(|theirs| f64::from(rules(theirs, yours)))
+                .collect()
+        })
+        .collect()
+}
+
+/// The payoff matrix of the strategy guide scoring rules.
+pub fn score_matrix() -> Vec<Vec<f64>> {
+    payoff_matrix(&HANDS, score)
+}
+
+/// Solves the game with the simplex method.
+///
+/// The payoffs are shifted to be strictly positive, after which the column
+/// player's strategy is the solution of `max sum(y)` subject to `A y <= 1`,
+/// and the row player's strategy is read from the dual prices of the slack
+/// variables in the final tableau.
+pub fn solve(payoffs: &[Vec<f64>]) -> Result<Equilibrium, &'static str> {
+    let rows = payoffs.len();
+    let cols = payoffs.first().map_or(0, Vec::len);
+
+    if rows == 0 || cols == 0 {
+        return Err("EmptyMatrix");
+    }
+    if payoffs.iter().any(|row| row.len() != cols) {
+        return Err("RaggedMatrix");
+    }
+    if payoffs.iter().flatten().any(|v| !v.is_finite()) {
+        return Err("NonFinitePayoff");
+    }
+
+    let min = payoffs
+        .iter()
+        .flatten()
+        .copied()
+        .fold(f64::INFINITY, f64::min);
+    let shift = 1.0 - min;
+
+    let width = cols + rows + 1;
+    let rhs = width - 1;
+
+    let mut tableau: Vec<Vec<f64>> = payoffs
+        .iter()
+        .enumerate()
+        .map(|(i, row)| {
+            let mut t = vec![0.0; width];
+            for (j, v) in row.iter().enumerate() {
+                t[j] = v + shift;
+            }
+            t[cols + i] = 1.0;
+            t[rhs] = 1.0;
+            t
+        })
+        .collect();
+
+    let mut objective = vec![0.0; width];
+    objective[..cols].iter_mut().for_each(|v| *v = -1.0);
+
+    let mut basis: Vec<usize> = (cols..cols + rows).collect();
+
+    // Bland's rule: lowest-index entering and leaving variables, which
+    // guarantees termination on degenerate games.
+    while let Some(entering) = (0..rhs).find(|&j| objective[j] < -EPSILON) {
+        let mut leaving: Option<usize> = None;
+        for r in 0..rows {
+            if tableau[r][entering] <= EPSILON {
+                continue;
+            }
+            let ratio = tableau[r][rhs] / tableau[r][entering];
+            let better = match leaving {
+                None => true,
+                Some(l) => {
+                    let best = tableau[l][rhs] / tableau[l][entering];
+                    ratio < best - EPSILON || (ratio < best + EPSILON && basis[r] < basis[l])
+                }
+            };
+            if better {
+                leaving = Some(r);
+            }
+        }
+
+        // The shifted payoffs are all positive, so the program is bounded.
+        let pivot_row = leaving.ok_or("Unbounded")?;
+        pivot(&mut tableau, &mut objective, pivot_row, entering);
+        basis[pivot_row] = entering;
+    }
+
+    let total = objective[rhs];
+    let value = 1.0 / total;
+
+    let mut column_strategy = vec![0.0; cols];
+    for (r, &var) in basis.iter().enumerate() {
+        if var < cols {
+            column_strategy[var] = tableau[r][rhs] * value;
+        }
+    }
+
+    let row_strategy = (0..rows).map(|i| objective[cols + i] * value).collect();
+
+    Ok(Equilibrium {
+        row_strategy,
+        column_strategy,
+        value: value - shift,
+    })
+}
+
+fn pivot(tableau: &mut [Vec<f64>], objective: &mut [f64], row: usize, col: usize) {
+    let divisor = tableau[row][col];
+    tableau[row].iter_mut().for_each(|v| *v /= divisor);
+
+    let pivot_row = tableau[row].clone();
+    let eliminate = |target: &mut [f64]| {
+        let factor = target[col];
+        if factor != 0.0 {
+            for (t, p) in target.iter_mut().zip(pivot_row.iter()) {
+                *t -= factor * p;
+            }
+        }
+    };
+
+    for (r, target) in tableau.iter_mut().enumerate() {
+        if r != row {
+            eliminate(target);
+        }
+    }
+    eliminate(objective);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: &[f64], expected: &[f64]) {
+        assert_eq!(actual.len(), expected.len());
+        for (a, e) in actual.iter().zip(expected.iter()) {
+            assert!((a - e).abs() < 1e-6, "{:?} != {:?}", actual, expected);
+        }
+    }
+
+    #[test]
+    fn test_matching_pennies() -> Result<(), &'static str> {
+        let eq = solve(&[vec![1.0, -1.0], vec![-1.0, 1.0]])?;
+        assert_close(&eq.row_strategy, &[0.5, 0.5]);
+        assert_close(&eq.column_strategy, &[0.5, 0.5]);
+        assert_close(&[eq.value], &[0.0]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_saddle_point() -> Result<(), &'static str> {
+        let eq = solve(&[vec![3.0, 5.0], vec![1.0, 4.0]])?;
+        assert_close(&eq.row_strategy, &[1.0, 0.0]);
+        assert_close(&eq.column_strategy, &[1.0, 0.0]);
+        assert_close(&[eq.value], &[3.0]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_classic_rock_paper_scissors() -> Result<(), &'static str> {
+        let eq = solve(&payoff_matrix(&HANDS, |theirs, yours| {
+            score(theirs, yours) - u32::from(yours)
+        }))?;
+        let third = 1.0 / 3.0;
+        assert_close(&eq.row_strategy, &[third, third, third]);
+        assert_close(&eq.column_strategy, &[third, third, third]);
+        assert_close(&[eq.value], &[3.0]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_score_matrix_equilibrium() -> Result<(), &'static str> {
+        let matrix = score_matrix();
+        assert_eq!(
+            matrix,
+            vec![
+                vec![4.0, 1.0, 7.0],
+                vec![8.0, 5.0, 2.0],
+                vec![3.0, 9.0, 6.0],
+            ]
+        );
+
+        let eq = solve(&matrix)?;
+        let third = 1.0 / 3.0;
+        assert_close(&eq.row_strategy, &[third, third, third]);
+        assert_close(&eq.column_strategy, &[4.0 / 9.0, 1.0 / 9.0, 4.0 / 9.0]);
+        assert_close(&[eq.value], &[5.0]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_matrix() {
+        assert_eq!(solve(&[]).unwrap_err(), "EmptyMatrix");
+        assert_eq!(
+            solve(&[vec![1.0, 2.0], vec![3.0]]).unwrap_err(),
+            "RaggedMatrix"
+        );
+    }
+}
@@ -0,0 +1,226 @@
+use std::io::{self, BufRead, Write};
+
+use crate::{nash, next_random, score, Game, Hand, HANDS};
+
+/// How the computer picks its shape each round.
+#[derive(Debug)]
+pub enum Strategy {
+    Always(Hand),
+    Cycle,
+    Mirror,
+    Random(u64),
+    /// Plays the opponent's side of the equilibrium of the scoring matrix,
+    /// which keeps your expected score at the game value.
+    Nash {
+        state: u64,
+        weights: [f64; 3],
+    },
+}
+
+impl TryFrom<&str> for Strategy {
+    type Error = &'static str;
+
+    fn try_from(string: &str) -> Result<Self, Self::Error> {
+        match string {
+            "rock" => Ok(Strategy::Always(Hand::Rock)),
+            "paper" => Ok(Strategy::Always(Hand::Paper)),
+            "scissors" => Ok(Strategy::Always(Hand::Sizzors)),
+            "cycle" => Ok(Strategy::Cycle),
+            "mirror" => Ok(Strategy::Mirror),
+            "random" => Ok(Strategy::Random(seed())),
+            "nash" => Strategy::nash(seed()),
+            _ => Err("UnknownStrategy"),
+        }
+    }
+}
+
+fn seed() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |d| d.as_nanos() as u64)
+}
+
+impl Strategy {
+    /// Solves the scoring matrix once for the weights of every later round.
+    pub fn nash(state: u64) -> Result<Self, &'static str> {
+        let eq = nash::solve(&nash::score_matrix())?;
+        let weights = eq
+            .column_strategy
+            .try_into()
+            .map_err(|_| "UnexpectedStrategySize")?;
+        Ok(Strategy::Nash { state, weights })
+    }
+
+    /// Picks the computer's shape given every shape you have played so far.
+    fn choose(&mut self, history: &[Hand]) -> Hand {
+        match self {
+            Strategy::Always(hand) => *hand,
+            Strategy::Cycle => HANDS[history.len() % 3],
+            Strategy::Mirror => history.last().copied().unwrap_or(Hand::Rock),
+            Strategy::Random(state) => HANDS[next_random(state) as usize % 3],
+            Strategy::Nash { state, weights } => {
+                let total: f64 = weights.iter().sum();
+                let mut roll = f64::from(next_random(state)) / f64::from(1u32 << 31) * total;
+                for (hand, weight) in HANDS.iter().zip(weights.iter()) {
+                    if roll < *weight {
+                        return *hand;
+                    }
+                    roll -= weight;
+                }
+                HANDS[2]
+            }
+        }
+    }
+}
+
+/// Running totals of a session.
+#[derive(Debug, Default, Eq, PartialEq)]
+pub struct Tally {
+    pub rounds: u32,
+    pub wins: u32,
+    pub draws: u32,
+    pub losses: u32,
+    pub yours: u32,
+    pub theirs: u32,
+}
+
+fn parse_move(string: &str) -> Option<Hand> {
+    match string.to_ascii_lowercase().as_str() {
+        "r" | "rock" => Some(Hand::Rock),
+        "p" | "paper" => Some(Hand::Paper),
+        "s" | "scissors" => Some(Hand::Sizzors),
+        other => Hand::try_from(other.to_ascii_uppercase().as_str()).ok(),
+    }
+}
+
+/// Plays rounds read from `input` until it ends or `quit` is entered,
+/// writing each round's score and the running tally to `output`.
+pub fn play<R, W>(input: R, mut output: W, strategy: &mut Strategy) -> io::Result<Tally>
+where
+    R: BufRead,
+    W: Write,
+{
+    let mut tally = Tally::default();
+    let mut history: Vec<Hand> = Vec::new();
+
+    writeln!(output, "Play rock, paper or scissors (r/p/s), or quit.")?;
+
+    for line in input.lines() {
+        let line = line?;
+        let line = line.trim();
+
+        if line.is_empty() {
+            continue;
+        }
+        if line == "quit" || line == "q" {
+            break;
+        }
+
+        let yours = match parse_move(line) {
+            Some(hand) => hand,
+            None => {
+                writeln!(output, "Unknown shape {:?}", line)?;
+                continue;
+            }
+        };
+        let theirs = strategy.choose(&history);
+        history.push(yours);
+
+        let game = yours.cmp(&theirs);
+        let points = score(&theirs, &yours);
+        let shape = u32::from(&yours);
+
+        tally.rounds += 1;
+        tally.yours += points;
+        tally.theirs += score(&yours, &theirs);
+        match game {
+            Game::Win => tally.wins += 1,
+            Game::Draw => tally.draws += 1,
+            Game::Lose => tally.losses += 1,
+        }
+
+        writeln!(
+            output,
+            "You played {:?}, they played {:?}: {:?}, {} + {} = {}",
+            yours,
+            theirs,
+            game,
+            shape,
+            points - shape,
+            points
+        )?;
+        writeln!(
+            output,
+            "Round {}: you {}, them {} ({} won, {} drawn, {} lost)",
+            tally.rounds, tally.yours, tally.theirs, tally.wins, tally.draws, tally.losses
+        )?;
+    }
+
+    Ok(tally)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(input: &str, mut strategy: Strategy) -> (Tally, String) {
+        let mut output = Vec::new();
+        let tally = play(input.as_bytes(), &mut output, &mut strategy).unwrap();
+        (tally, String::from_utf8(output).unwrap())
+    }
+
+    #[test]
+    fn test_parse_move() {
+        assert_eq!(parse_move("Rock"), Some(Hand::Rock));
+        assert_eq!(parse_move("p"), Some(Hand::Paper));
+        assert_eq!(parse_move("z"), Some(Hand::Sizzors));
+        assert_eq!(parse_move("lizard"), None);
+    }
+
+    #[test]
+    fn test_play_against_rock() {
+        let (tally, output) = run("paper\nrock\nscissors\n", Strategy::Always(Hand::Rock));
+
+        assert_eq!(
+            tally,
+            Tally {
+                rounds: 3,
+                wins: 1,
+                draws: 1,
+                losses: 1,
+                yours: 8 + 4 + 3,
+                theirs: 1 + 4 + 7,
+            }
+        );
+        assert!(output.contains("You played Paper, they played Rock: Win, 2 + 6 = 8"));
+        assert!(output.contains("Round 3: you 15, them 12 (1 won, 1 drawn, 1 lost)"));
+    }
+
+    #[test]
+    fn test_play_stops_on_quit() {
+        let (tally, output) = run("r\nbanana\nquit\nr\n", Strategy::Mirror);
+
+        assert_eq!(tally.rounds, 1);
+        assert_eq!(tally.draws, 1);
+        assert!(output.contains("Unknown shape \"banana\""));
+    }
+
+    #[test]
+    fn test_strategies() {
+        let mut cycle = Strategy::Cycle;
+        let history = [Hand::Rock, Hand::Rock];
+        assert_eq!(cycle.choose(&history[..0]), Hand::Rock);
+        assert_eq!(cycle.choose(&history[..1]), Hand::Paper);
+        assert_eq!(cycle.choose(&history), Hand::Sizzors);
+
+        let mut mirror = Strategy::Mirror;
+        assert_eq!(mirror.choose(&[Hand::Paper]), Hand::Paper);
+
+        let mut nash = Strategy::nash(7).unwrap();
+        assert!(matches!(nash, Strategy::Nash { weights, .. } if weights[1] < weights[2]));
+        let picks: Vec<Hand> = (0..900).map(|_| nash.choose(&[])).collect();
+        let count = |hand| picks.iter().filter(|&&h| h == hand).count();
+        assert!((50..150).contains(&count(Hand::Paper)));
+        assert!((300..500).contains(&count(Hand::Sizzors)));
+    }
+}
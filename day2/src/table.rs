@@ -0,0 +1,185 @@
+use crate::{next_random, score_line, Part};
+
+/// Scores indexed by `[theirs][column]`, where `theirs` is `A`, `B`, `C` and
+/// `column` is `X`, `Y`, `Z`, both counted from zero.
+pub type ScoreTable = [[u32; 3]; 3];
+
+const fn outcome_points(theirs: usize, yours: usize) -> u32 {
+    match (yours + 3 - theirs) % 3 {
+        0 => 3,
+        1 => 6,
+        _ => 0,
+    }
+}
+
+const fn build_part1() -> ScoreTable {
+    let mut table = [[0; 3]; 3];
+    let mut theirs = 0;
+    while theirs < 3 {
+        let mut yours = 0;
+        while yours < 3 {
+            table[theirs][yours] = yours as u32 + 1 + outcome_points(theirs, yours);
+            yours += 1;
+        }
+        theirs += 1;
+    }
+    table
+}
+
+const fn build_part2() -> ScoreTable {
+    let mut table = [[0; 3]; 3];
+    let mut theirs = 0;
+    while theirs < 3 {
+        let mut outcome = 0;
+        while outcome < 3 {
+            // Lose, draw and win are one shape behind, level with and one
+            // shape ahead of the opponent.
+            let yours = (theirs + outcome + 2) % 3;
+            table[theirs][outcome] = yours as u32 + 1 + outcome as u32 * 3;
+            outcome += 1;
+        }
+        theirs += 1;
+    }
+    table
+}
+
+pub const PART1: ScoreTable = build_part1();
+pub const PART2: ScoreTable = build_part2();
+
+/// Scores a raw strategy guide for both parts in a single pass.
+///
+/// Lines that are exactly one of `A`-`C`, a single space and one of `X`-`Z`
+/// are looked up directly; any other line is handed to `score_line`, so a
+/// guide scores here exactly when both parts can score it there.
+pub fn score_bytes(input: &[u8]) -> Result<(u32, u32), &'static str> {
+    let mut totals = (0, 0);
+
+    for line in input.split_inclusive(|&b| b == b'\n') {
+        let line = match line.strip_suffix(b"\n") {
+            Some(line) => line.strip_suffix(b"\r").unwrap_or(line),
+            None => line,
+        };
+        match *line {
+            [theirs @ b'A'..=b'C', b' ', column @ b'X'..=b'Z'] => {
+                let (t, c) = ((theirs - b'A') as usize, (column - b'X') as usize);
+                totals.0 += PART1[t][c];
+                totals.1 += PART2[t][c];
+            }
+            _ => {
+                let line = std::str::from_utf8(line).map_err(|_| "ParseError")?;
+                let part1 = score_line(line, &Part::Part1).map_err(|_| "ParseError")?;
+                let part2 = score_line(line, &Part::Part2).map_err(|_| "ParseError")?;
+                totals.0 += part1;
+                totals.1 += part2;
+            }
+        }
+    }
+
+    Ok(totals)
+}
+
+/// Generates a well-formed guide of `rounds` lines, reproducible from the
+/// seed.
+pub fn synthetic_guide(rounds: usize, seed: u64) -> String {
+    let mut state = seed;
+    let mut guide = String::with_capacity(rounds * 4);
+
+    for _ in 0..rounds {
+        guide.push((b'A' + (next_random(&mut state) % 3) as u8) as char);
+        guide.push(' ');
+        guide.push((b'X' + (next_random(&mut state) % 3) as u8) as char);
+        guide.push('\n');
+    }
+
+    guide
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{score, score_lines, HANDS};
+    use std::error::Error;
+
+    #[test]
+    fn test_part1_table_matches_score() {
+        for (t, theirs) in HANDS.iter().enumerate() {
+            for (y, yours) in HANDS.iter().enumerate() {
+                assert_eq!(PART1[t][y], score(theirs, yours));
+            }
+        }
+    }
+
+    #[test]
+    fn test_tables_match_score_lines() -> Result<(), Box<dyn Error>> {
+        for (t, theirs) in ["A", "B", "C"].iter().enumerate() {
+            for (c, column) in ["X", "Y", "Z"].iter().enumerate() {
+                let line = [format!("{} {}", theirs, column)];
+                let lines: Vec<&str> = line.iter().map(String::as_str).collect();
+                assert_eq!(PART1[t][c], score_lines(&lines, &Part::Part1)?);
+                assert_eq!(PART2[t][c], score_lines(&lines, &Part::Part2)?);
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_score_bytes() {
+        let input = include_str!("../data/input_test.txt");
+        assert_eq!(score_bytes(input.as_bytes()), Ok((15, 12)));
+
+        assert_eq!(score_bytes(b"A Y\r\nB X\n"), Ok((9, 5)));
+        assert_eq!(score_bytes(b"A Y\r\n\nB X\n"), Err("ParseError"));
+        assert_eq!(score_bytes(b""), Ok((0, 0)));
+        assert_eq!(score_bytes(b"A\n"), Err("ParseError"));
+        assert_eq!(score_bytes(b"D Q\n"), Err("ParseError"));
+        assert_eq!(score_bytes(b"A Y Z\n"), Err("ParseError"));
+        assert_eq!(score_bytes(b"A  Y\nB X \n"), Ok((9, 5)));
+    }
+
+    #[test]
+    fn test_score_bytes_matches_score_line_on_irregular_rows() {
+        let rows = [
+            "AY", "A  Y", "A\tY", " A Y", "A Y ", "A Y Z", "A Y\tZ", "A", "", "D X", "A W", "A A",
+            "a y",
+        ];
+        for row in rows {
+            for guide in [row.to_string(), format!("A Y\n{}\nB X\n", row)] {
+                let scored = [Part::Part1, Part::Part2]
+                    .iter()
+                    .all(|part| guide.lines().all(|line| score_line(line, part).is_ok()));
+                assert_eq!(score_bytes(guide.as_bytes()).is_ok(), scored, "{:?}", guide);
+            }
+        }
+    }
+
+    #[test]
+    fn test_synthetic_guide_is_uniform() {
+        let guide = synthetic_guide(90_000, 2022);
+        let mut counts = [[0usize; 3]; 3];
+        for line in guide.lines() {
+            let b = line.as_bytes();
+            counts[(b[0] - b'A') as usize][(b[2] - b'X') as usize] += 1;
+        }
+        for count in counts.iter().flatten() {
+            assert!(count.abs_diff(10_000) < 500, "{:?}", counts);
+        }
+    }
+
+    #[test]
+    fn test_score_bytes_matches_score_lines() -> Result<(), Box<dyn Error>> {
+        for input in [
+            include_str!("../data/input.txt").to_string(),
+            synthetic_guide(100_000, 2022),
+        ] {
+            let lines: Vec<&str> = input.lines().collect();
+            assert_eq!(
+                score_bytes(input.as_bytes()),
+                Ok((
+                    score_lines(&lines, &Part::Part1)?,
+                    score_lines(&lines, &Part::Part2)?
+                ))
+            );
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,162 @@
+use std::error::Error;
+use std::fmt;
+
+use crate::{columns, score_line, Game, Hand, Part};
+
+/// A strategy guide line that could not be scored.
+#[derive(Debug, Eq, PartialEq)]
+pub struct Diagnostic {
+    pub line_number: usize,
+    pub line: String,
+    pub reason: String,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}: {} ({:?})",
+            self.line_number, self.reason, self.line
+        )
+    }
+}
+
+impl Error for Diagnostic {}
+
+/// The score of every well-formed line, along with the lines that were
+/// skipped.
+#[derive(Debug)]
+pub struct LenientScore {
+    pub score: u32,
+    pub skipped: Vec<Diagnostic>,
+}
+
+/// Why a line cannot be scored, naming the part when only one of them
+/// rejects it.
+fn check_line(line: &str) -> Result<(), String> {
+    let (first, second) = columns(line)?;
+    Hand::try_from(first).map_err(|_| "InvalidOpponentShape")?;
+
+    // Part 1 reads the second column as a shape, part 2 as an outcome.
+    match (
+        Hand::try_from(second).is_ok(),
+        Game::try_from(second).is_ok(),
+    ) {
+        (true, true) => Ok(()),
+        (true, false) => Err("InvalidResponse in part 2".to_string()),
+        (false, true) => Err("InvalidResponse in part 1".to_string()),
+        (false, false) => Err("InvalidResponse".to_string()),
+    }
+}
+
+/// Reports every line that either part cannot score, once, numbered from 1.
+pub fn validate_guide(lines: &[&str]) -> Vec<Diagnostic> {
+    lines
+        .iter()
+        .enumerate()
+        .filter_map(|(i, line)| {
+            check_line(line).err().map(|reason| Diagnostic {
+                line_number: i + 1,
+                line: line.to_string(),
+                reason,
+            })
+        })
+        .collect()
+}
+
+pub fn score_lines_lenient(lines: &[&str], part: &Part) -> LenientScore {
+    let mut result = LenientScore {
+        score: 0,
+        skipped: Vec::new(),
+    };
+
+    for (i, line) in lines.iter().enumerate() {
+        match score_line(line, part) {
+            Ok(score) => result.score += score,
+            Err(e) => result.skipped.push(Diagnostic {
+                line_number: i + 1,
+                line: line.to_string(),
+                reason: e.to_string(),
+            }),
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_clean_input() {
+        let input = include_str!("../data/input_test.txt");
+        let lines: Vec<&str> = input.lines().collect();
+        assert!(validate_guide(&lines).is_empty());
+        assert!(score_lines_lenient(&lines, &Part::Part1).skipped.is_empty());
+        assert!(score_lines_lenient(&lines, &Part::Part2).skipped.is_empty());
+    }
+
+    #[test]
+    fn test_validate_reports_every_line() {
+        let lines = ["A Y", "D Q", "B", "", "C Z X", "A Q"];
+        let reasons: Vec<(usize, String)> = score_lines_lenient(&lines, &Part::Part2)
+            .skipped
+            .into_iter()
+            .map(|d| (d.line_number, d.reason))
+            .collect();
+
+        assert_eq!(
+            reasons,
+            vec![
+                (2, "InvalidOpponentShape".to_string()),
+                (3, "MissingColumn".to_string()),
+                (4, "MissingColumn".to_string()),
+                (5, "TrailingColumn".to_string()),
+                (6, "InvalidResponse".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_validate_guide() {
+        let lines = ["A Y", "D Q", "A A", "B", "C Z"];
+        let diagnostics = validate_guide(&lines);
+        let reasons: Vec<String> = diagnostics.iter().map(|d| d.to_string()).collect();
+        assert_eq!(
+            reasons,
+            vec![
+                "line 2: InvalidOpponentShape (\"D Q\")",
+                "line 3: InvalidResponse in part 2 (\"A A\")",
+                "line 4: MissingColumn (\"B\")",
+            ]
+        );
+
+        // Agrees with each part's own validation.
+        for (i, line) in lines.iter().enumerate() {
+            let malformed = diagnostics.iter().any(|d| d.line_number == i + 1);
+            let rejected = [Part::Part1, Part::Part2]
+                .iter()
+                .any(|part| score_line(line, part).is_err());
+            assert_eq!(malformed, rejected, "{:?}", line);
+        }
+
+        assert_eq!(validate_guide(&["C Z X"]).len(), 1);
+    }
+
+    #[test]
+    fn test_score_lines_lenient() {
+        let lines = ["A Y", "D Q", "B X", "C Z", "C"];
+
+        let result = score_lines_lenient(&lines, &Part::Part1);
+        assert_eq!(result.score, 15);
+        assert_eq!(result.skipped.len(), 2);
+
+        let result = score_lines_lenient(&lines, &Part::Part2);
+        assert_eq!(result.score, 12);
+        assert_eq!(
+            result.skipped[0].to_string(),
+            "line 2: InvalidOpponentShape (\"D Q\")"
+        );
+    }
+}
@@ -0,0 +1,144 @@
+use crate::item_set::ItemSet;
+
+const MAX_ITEMS: usize = 63;
+
+/// The valid item types and their priorities, where the item at index `i`
+/// has priority `i + 1`.
+///
+/// Items are ASCII characters, and there can be at most 63 of them so that
+/// every priority fits in an `ItemSet`.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct Alphabet {
+    priorities: [u8; 128],
+    items: [u8; MAX_ITEMS],
+    len: usize,
+}
+
+impl Alphabet {
+    /// `a` through `z` with priorities 1 through 26, then `A` through `Z`
+    /// with priorities 27 through 52.
+    pub const fn letters() -> Self {
+        let mut alphabet = Alphabet {
+            priorities: [0; 128],
+            items: [0; MAX_ITEMS],
+            len: 52,
+        };
+
+        let mut i = 0;
+        while i < 26 {
+            alphabet.items[i] = b'a' + i as u8;
+            alphabet.items[i + 26] = b'A' + i as u8;
+            alphabet.priorities[(b'a' + i as u8) as usize] = i as u8 + 1;
+            alphabet.priorities[(b'A' + i as u8) as usize] = i as u8 + 27;
+            i += 1;
+        }
+
+        alphabet
+    }
+
+    pub fn new(items: &str) -> Result<Self, &'static str> {
+        let mut alphabet = Alphabet {
+            priorities: [0; 128],
+            items: [0; MAX_ITEMS],
+            len: 0,
+        };
+
+        for c in items.chars() {
+            if !c.is_ascii() || c.is_ascii_control() {
+                return Err("InvalidAlphabetItem");
+            }
+            if alphabet.len == MAX_ITEMS {
+                return Err("AlphabetTooLarge");
+            }
+            if alphabet.priorities[c as usize] != 0 {
+                return Err("DuplicateAlphabetItem");
+            }
+
+            alphabet.items[alphabet.len] = c as u8;
+            alphabet.len += 1;
+            alphabet.priorities[c as usize] = alphabet.len as u8;
+        }
+
+        match alphabet.len {
+            0 => Err("EmptyAlphabet"),
+            _ => Ok(alphabet),
+        }
+    }
+
+    pub fn priority(&self, item: char) -> Option<u32> {
+        match self.priorities.get(item as usize) {
+            Some(&p) if p != 0 => Some(u32::from(p)),
+            _ => None,
+        }
+    }
+
+    pub fn item(&self, priority: u32) -> Option<char> {
+        match priority as usize {
+            p if p >= 1 && p <= self.len => Some(char::from(self.items[p - 1])),
+            _ => None,
+        }
+    }
+
+    /// Collects the items into a set, or returns the first item that is not
+    /// part of the alphabet.
+    pub fn item_set(&self, items: &str) -> Result<ItemSet, char> {
+        let mut set = ItemSet::empty();
+        for c in items.chars() {
+            set.insert(self.priority(c).ok_or(c)?);
+        }
+        Ok(set)
+    }
+}
+
+impl Default for Alphabet {
+    fn default() -> Self {
+        Alphabet::letters()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_letters() {
+        let letters = Alphabet::letters();
+        assert_eq!(letters.priority('a'), Some(1));
+        assert_eq!(letters.priority('p'), Some(16));
+        assert_eq!(letters.priority('A'), Some(27));
+        assert_eq!(letters.priority('Z'), Some(52));
+
+        for c in ('a'..='z').chain('A'..='Z') {
+            assert_eq!(letters.item(letters.priority(c).unwrap()), Some(c));
+        }
+        assert_eq!(letters.item(0), None);
+        assert_eq!(letters.item(53), None);
+    }
+
+    #[test]
+    fn test_letters_reject_other_items() {
+        let letters = Alphabet::letters();
+        for c in ['0', '!', ' ', '[', '`', 'é', '\u{1F392}'] {
+            assert_eq!(letters.priority(c), None);
+        }
+        assert_eq!(letters.item_set("ab1c"), Err('1'));
+    }
+
+    #[test]
+    fn test_custom_alphabet() -> Result<(), &'static str> {
+        let digits = Alphabet::new("0123456789")?;
+        assert_eq!(digits.priority('0'), Some(1));
+        assert_eq!(digits.priority('9'), Some(10));
+        assert_eq!(digits.priority('a'), None);
+        assert_eq!(digits.item(3), Some('2'));
+
+        assert_eq!(Alphabet::new(""), Err("EmptyAlphabet"));
+        assert_eq!(Alphabet::new("abca"), Err("DuplicateAlphabetItem"));
+        assert_eq!(Alphabet::new("é"), Err("InvalidAlphabetItem"));
+
+        let wide: String = (b'!'..=b'`').map(char::from).collect();
+        assert_eq!(Alphabet::new(&wide[1..]).map(|a| a.len), Ok(63));
+        assert_eq!(Alphabet::new(&wide), Err("AlphabetTooLarge"));
+        Ok(())
+    }
+}
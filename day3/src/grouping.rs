@@ -0,0 +1,425 @@
+use std::cmp::Reverse;
+
+use crate::alphabet::Alphabet;
+use crate::item_set::ItemSet;
+use crate::rucksack::{check_groups, find_badge, Rucksack, RucksackError};
+
+/// A group found by `discover_badges`, with the item its members share.
+#[derive(Debug, Eq, PartialEq)]
+pub struct Group {
+    /// The members' indices, in order.
+    pub members: Vec<usize>,
+    pub badge: char,
+    pub priority: u32,
+}
+
+/// Partitions the rucksacks into groups of `group_size` so that every group
+/// shares exactly one item type, returning the indices of each group's
+/// members, or `None` when no such partition exists.
+///
+/// This is an exact cover search that never lists every possible group.
+/// Groups are built one at a time around an unassigned rucksack, from the
+/// rucksacks it still shares an item with, and a partial group is dropped as
+/// soon as its members have nothing in common. Rucksacks with the most item
+/// types are placed first, as they have the fewest groups. Once few are
+/// left, each step counts the groups left to every unassigned rucksack, up
+/// to a small limit, abandoning the branch if one has none and otherwise
+/// placing the rucksack with the fewest.
+pub fn discover(rucksacks: &[ItemSet], group_size: usize) -> Option<Vec<Vec<usize>>> {
+    if group_size == 0 || !rucksacks.len().is_multiple_of(group_size) {
+        return None;
+    }
+
+    let mut groups = Search::new(rucksacks, group_size).solve()?;
+    for group in &mut groups {
+        group.sort();
+    }
+    groups.sort();
+
+    Some(groups)
+}
+
+/// Regroups the rucksacks regardless of their order, as `discover` does, and
+/// finds each group's badge, or `None` when no grouping exists.
+pub fn discover_badges(
+    rucksacks: &[Rucksack],
+    group_size: usize,
+    alphabet: &Alphabet,
+) -> Result<Option<Vec<Group>>, RucksackError> {
+    check_groups(rucksacks.len(), group_size)?;
+
+    let items: Vec<ItemSet> = rucksacks.iter().map(Rucksack::items).collect();
+    let Some(found) = discover(&items, group_size) else {
+        return Ok(None);
+    };
+
+    let mut groups = Vec::with_capacity(found.len());
+    for members in found {
+        let line_number = rucksacks[members[0]].line_number();
+        let badge = find_badge(members.iter().map(|&i| &rucksacks[i]), alphabet)
+            .into_result(line_number)?;
+        groups.push(Group {
+            members,
+            badge,
+            priority: alphabet.priority(badge).unwrap_or_default(),
+        });
+    }
+
+    Ok(Some(groups))
+}
+
+/// The badge priorities of every group, summed.
+pub fn badge_priority_sum(groups: &[Group]) -> u32 {
+    groups.iter().map(|g| g.priority).sum()
+}
+
+/// How many rucksacks are left when the search starts placing the most
+/// constrained one next rather than the next in order.
+const ENDGAME: usize = 150;
+
+/// How many groups a rucksack needs to be no more pressing than any other.
+const ENOUGH_GROUPS: usize = 10;
+
+/// A set of rucksack indices, one bit each.
+struct Bits(Vec<u64>);
+
+impl Bits {
+    fn full(len: usize) -> Self {
+        let mut bits = Bits(vec![0; len.div_ceil(64)]);
+        for i in 0..len {
+            bits.insert(i);
+        }
+        bits
+    }
+
+    fn insert(&mut self, i: usize) {
+        self.0[i / 64] |= 1 << (i % 64);
+    }
+
+    fn remove(&mut self, i: usize) {
+        self.0[i / 64] &= !(1 << (i % 64));
+    }
+
+    /// The smallest index in the set that is at least `from`.
+    fn next(&self, from: usize) -> Option<usize> {
+        let mut w = from / 64;
+        let mut word = self.0.get(w)? & (u64::MAX << (from % 64));
+        while word == 0 {
+            w += 1;
+            word = *self.0.get(w)?;
+        }
+        Some(w * 64 + word.trailing_zeros() as usize)
+    }
+}
+
+/// The groups one rucksack can join, listed lazily in order of their
+/// members' indices.
+struct Candidates {
+    /// The rucksack, then the partners picked so far.
+    members: Vec<usize>,
+    /// The items `members[..=i]` have in common, at `i`.
+    common: Vec<ItemSet>,
+    started: bool,
+}
+
+impl Candidates {
+    fn new(rucksack: usize, items: ItemSet) -> Self {
+        Candidates {
+            members: vec![rucksack],
+            common: vec![items],
+            started: false,
+        }
+    }
+
+    fn pop(&mut self) -> usize {
+        self.common.pop();
+        self.members.pop().expect("a partner was picked")
+    }
+
+    /// Moves on to the next group of `group_size` unassigned rucksacks that
+    /// share exactly one item, returning `false` once there are none left.
+    fn advance(&mut self, rucksacks: &[ItemSet], unassigned: &Bits, group_size: usize) -> bool {
+        let mut from = 0;
+        // Copies of the partner just dropped would only lead to the same groups.
+        let mut tried = None;
+        if self.members.len() == group_size {
+            if !self.started {
+                self.started = true;
+                return self.common[0].len() == 1;
+            }
+            if group_size == 1 {
+                return false;
+            }
+            let last = self.pop();
+            from = last + 1;
+            tried = Some(rucksacks[last]);
+        }
+        self.started = true;
+
+        loop {
+            let Some(next) = unassigned.next(from) else {
+                if self.members.len() == 1 {
+                    return false;
+                }
+                let last = self.pop();
+                from = last + 1;
+                tried = Some(rucksacks[last]);
+                continue;
+            };
+            from = next + 1;
+            if next == self.members[0] || tried == Some(rucksacks[next]) {
+                continue;
+            }
+
+            let shared = self.common[self.common.len() - 1] & rucksacks[next];
+            let last = self.members.len() + 1 == group_size;
+            if shared.is_empty() || (last && shared.len() != 1) {
+                continue;
+            }
+
+            self.members.push(next);
+            self.common.push(shared);
+            tried = None;
+            if last {
+                return true;
+            }
+        }
+    }
+}
+
+/// What the search should do with the rucksacks still unassigned.
+enum Step {
+    Done,
+    /// Some rucksack has no group left, so an earlier group has to change.
+    Stuck,
+    Place(usize),
+}
+
+struct Search {
+    /// The rucksacks in the order they are placed.
+    rucksacks: Vec<ItemSet>,
+    /// The index each rucksack was given as.
+    indices: Vec<usize>,
+    group_size: usize,
+    unassigned: Bits,
+    left: usize,
+}
+
+impl Search {
+    /// Orders the rucksacks so those with the most item types, which share
+    /// exactly one item with the fewest others, are placed first. Copies of
+    /// a rucksack are spread out so the last ones left are not all alike.
+    fn new(rucksacks: &[ItemSet], group_size: usize) -> Self {
+        let mut indices: Vec<usize> = (0..rucksacks.len()).collect();
+        indices.sort_by_key(|&i| rucksacks[i]);
+        let mut copy = vec![0; rucksacks.len()];
+        for pair in indices.windows(2) {
+            if rucksacks[pair[0]] == rucksacks[pair[1]] {
+                copy[pair[1]] = copy[pair[0]] + 1;
+            }
+        }
+        indices.sort_by_key(|&i| (copy[i], Reverse(rucksacks[i].len())));
+
+        Search {
+            rucksacks: indices.iter().map(|&i| rucksacks[i]).collect(),
+            indices,
+            group_size,
+            unassigned: Bits::full(rucksacks.len()),
+            left: rucksacks.len(),
+        }
+    }
+
+    /// Picks the first unassigned rucksack, or near the end the one with the
+    /// fewest groups left, counting no further than `ENOUGH_GROUPS`.
+    fn step(&self) -> Step {
+        if self.left > ENDGAME {
+            return self.unassigned.next(0).map_or(Step::Done, Step::Place);
+        }
+
+        let mut fewest: Option<(usize, usize)> = None;
+        let mut from = 0;
+        while let Some(rucksack) = self.unassigned.next(from) {
+            from = rucksack + 1;
+
+            let mut candidates = Candidates::new(rucksack, self.rucksacks[rucksack]);
+            let mut count = 0;
+            while count < ENOUGH_GROUPS
+                && candidates.advance(&self.rucksacks, &self.unassigned, self.group_size)
+            {
+                count += 1;
+            }
+            if count == 0 {
+                return Step::Stuck;
+            }
+            if fewest.is_none_or(|(_, fewest)| count < fewest) {
+                fewest = Some((rucksack, count));
+            }
+        }
+
+        match fewest {
+            Some((rucksack, _)) => Step::Place(rucksack),
+            None => Step::Done,
+        }
+    }
+
+    /// Places one group at a time, keeping every placement's remaining
+    /// candidates on a stack to come back to when a later one gets stuck.
+    fn solve(mut self) -> Option<Vec<Vec<usize>>> {
+        let mut placed: Vec<Candidates> = Vec::new();
+        loop {
+            match self.step() {
+                Step::Done => break,
+                Step::Stuck => {}
+                Step::Place(rucksack) => {
+                    placed.push(Candidates::new(rucksack, self.rucksacks[rucksack]));
+                }
+            }
+
+            loop {
+                let top = placed.last_mut()?;
+                if top.started && top.members.len() == self.group_size {
+                    for &member in &top.members {
+                        self.unassigned.insert(member);
+                    }
+                    self.left += self.group_size;
+                }
+                if top.advance(&self.rucksacks, &self.unassigned, self.group_size) {
+                    for &member in &top.members {
+                        self.unassigned.remove(member);
+                    }
+                    self.left -= self.group_size;
+                    break;
+                }
+                placed.pop();
+            }
+        }
+
+        let groups = placed
+            .into_iter()
+            .map(|c| c.members.iter().map(|&m| self.indices[m]).collect())
+            .collect();
+        Some(groups)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layout::Layout;
+    use crate::rucksack::parse_rucksacks;
+    use std::time::{Duration, Instant};
+
+    fn item_sets(lines: &[&str]) -> Vec<ItemSet> {
+        let letters = Alphabet::letters();
+        lines.iter().map(|l| letters.item_set(l).unwrap()).collect()
+    }
+
+    fn check(rucksacks: &[ItemSet], groups: &[Vec<usize>], group_size: usize) {
+        let mut members: Vec<usize> = groups.iter().flatten().copied().collect();
+        members.sort();
+        assert_eq!(members, (0..rucksacks.len()).collect::<Vec<usize>>());
+
+        for group in groups {
+            assert_eq!(group.len(), group_size);
+            let common = group
+                .iter()
+                .fold(rucksacks[group[0]], |acc, &i| acc & rucksacks[i]);
+            assert_eq!(common.len(), 1);
+        }
+    }
+
+    #[test]
+    fn test_discover_shuffled_test_input() {
+        let lines: Vec<&str> = include_str!("../data/input_test.txt").lines().collect();
+        let shuffled = [lines[3], lines[0], lines[5], lines[2], lines[4], lines[1]];
+        let rucksacks = item_sets(&shuffled);
+
+        let groups = discover(&rucksacks, 3).unwrap();
+        check(&rucksacks, &groups, 3);
+    }
+
+    #[test]
+    fn test_discover_shuffled_input() {
+        let lines: Vec<&str> = include_str!("../data/input.txt").lines().collect();
+        let mut shuffled = Vec::with_capacity(lines.len());
+        for stride in 0..7 {
+            shuffled.extend(lines.iter().skip(stride).step_by(7));
+        }
+        let rucksacks = item_sets(&shuffled);
+
+        let groups = discover(&rucksacks, 3).unwrap();
+        check(&rucksacks, &groups, 3);
+    }
+
+    #[test]
+    fn test_discover_large_shuffled_input() {
+        let lines: Vec<&str> = include_str!("../data/input.txt").lines().collect();
+        let mut shuffled: Vec<&str> = lines
+            .iter()
+            .cycle()
+            .take(lines.len() * 10)
+            .copied()
+            .collect();
+        let mut state: u64 = 7;
+        for i in (1..shuffled.len()).rev() {
+            state = state
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            shuffled.swap(i, (state >> 33) as usize % (i + 1));
+        }
+        let rucksacks = item_sets(&shuffled);
+
+        let start = Instant::now();
+        let groups = discover(&rucksacks, 3).unwrap();
+        assert!(
+            start.elapsed() < Duration::from_secs(10),
+            "{:?}",
+            start.elapsed()
+        );
+        check(&rucksacks, &groups, 3);
+    }
+
+    #[test]
+    fn test_discover_badges() -> Result<(), RucksackError> {
+        let letters = Alphabet::letters();
+        let input = include_str!("../data/input_test.txt");
+        let rucksacks = parse_rucksacks(input, &Layout::halves(), &letters)?;
+
+        let groups = discover_badges(&rucksacks, 3, &letters)?.unwrap();
+        assert_eq!(groups.len(), 2);
+        for group in &groups {
+            let badge = letters.item_set(&group.badge.to_string()).unwrap();
+            let common = group
+                .members
+                .iter()
+                .fold(badge, |acc, &i| acc & rucksacks[i].items());
+            assert_eq!(common, badge);
+            assert_eq!(group.priority, letters.priority(group.badge).unwrap());
+        }
+        assert_eq!(
+            badge_priority_sum(&groups),
+            groups.iter().map(|g| g.priority).sum::<u32>()
+        );
+
+        assert!(matches!(
+            discover_badges(&rucksacks[..5], 3, &letters),
+            Err(RucksackError::IncompleteGroup { members: 2, .. })
+        ));
+        assert_eq!(
+            discover_badges(&rucksacks, 0, &letters),
+            Err(RucksackError::InvalidGroupSize)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_discover_impossible() {
+        let rucksacks = item_sets(&["ab", "ab", "ab"]);
+        assert_eq!(discover(&rucksacks, 3), None);
+
+        let rucksacks = item_sets(&["ab", "cd", "ad", "bc"]);
+        check(&rucksacks, &discover(&rucksacks, 2).unwrap(), 2);
+        assert_eq!(discover(&rucksacks, 3), None);
+        assert_eq!(discover(&rucksacks, 0), None);
+    }
+}
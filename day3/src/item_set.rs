@@ -0,0 +1,125 @@
+use std::ops::{BitAnd, BitOr};
+
+/// A set of item types stored as a bitmask, where bit `p` is set when the
+/// item with priority `p` is present.
+///
+/// Priorities must be below 64, which covers the 52 letter item types.
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq, Ord, PartialOrd)]
+pub struct ItemSet(u64);
+
+impl ItemSet {
+    pub const fn empty() -> Self {
+        ItemSet(0)
+    }
+
+    pub fn insert(&mut self, priority: u32) {
+        debug_assert!(priority < 64, "priority {} out of range", priority);
+        self.0 |= 1 << priority;
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// Iterates over the priorities in the set, lowest first.
+    pub fn iter(&self) -> Iter {
+        Iter(self.0)
+    }
+}
+
+pub struct Iter(u64);
+
+impl Iterator for Iter {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<u32> {
+        if self.0 == 0 {
+            return None;
+        }
+        let priority = self.0.trailing_zeros();
+        self.0 &= self.0 - 1;
+        Some(priority)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.0.count_ones() as usize;
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for Iter {}
+
+impl IntoIterator for ItemSet {
+    type Item = u32;
+    type IntoIter = Iter;
+
+    fn into_iter(self) -> Iter {
+        self.iter()
+    }
+}
+
+impl FromIterator<u32> for ItemSet {
+    fn from_iter<I: IntoIterator<Item = u32>>(iter: I) -> Self {
+        let mut set = ItemSet::empty();
+        for priority in iter {
+            set.insert(priority);
+        }
+        set
+    }
+}
+
+impl BitAnd for ItemSet {
+    type Output = ItemSet;
+
+    fn bitand(self, rhs: Self) -> ItemSet {
+        ItemSet(self.0 & rhs.0)
+    }
+}
+
+impl BitOr for ItemSet {
+    type Output = ItemSet;
+
+    fn bitor(self, rhs: Self) -> ItemSet {
+        ItemSet(self.0 | rhs.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_insert() {
+        let mut set = ItemSet::empty();
+        assert!(set.is_empty());
+
+        set.insert(1);
+        set.insert(52);
+        set.insert(52);
+
+        assert_eq!(set.len(), 2);
+        assert_eq!(set.iter().collect::<Vec<u32>>(), vec![1, 52]);
+    }
+
+    #[test]
+    fn test_set_operations() {
+        let lhs: ItemSet = [1, 2, 3].into_iter().collect();
+        let rhs: ItemSet = [2, 3, 4].into_iter().collect();
+
+        assert_eq!((lhs & rhs).iter().collect::<Vec<u32>>(), vec![2, 3]);
+        assert_eq!((lhs | rhs).iter().collect::<Vec<u32>>(), vec![1, 2, 3, 4]);
+        assert_eq!(lhs & rhs, rhs & lhs);
+        assert_eq!(lhs & ItemSet::empty(), ItemSet::empty());
+    }
+
+    #[test]
+    fn test_iter_in_priority_order() {
+        let set: ItemSet = [52, 27, 1, 26].into_iter().collect();
+        assert_eq!(set.iter().len(), 4);
+        assert_eq!(set.into_iter().collect::<Vec<u32>>(), vec![1, 26, 27, 52]);
+    }
+}
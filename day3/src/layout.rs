@@ -0,0 +1,142 @@
+use crate::item_set::ItemSet;
+
+/// Which item types count as common to a rucksack's compartments.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Sharing {
+    /// Carried in every compartment.
+    All,
+    /// Carried in at least two compartments.
+    AnyTwo,
+}
+
+/// The most compartments a layout can have, so a rucksack can keep every
+/// compartment inline.
+pub const MAX_COMPARTMENTS: usize = 8;
+
+/// How a rucksack line splits into equally sized compartments, and which of
+/// their items count as common.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct Layout {
+    compartments: usize,
+    sharing: Sharing,
+}
+
+impl Layout {
+    /// Two halves sharing the items found in both, as in the puzzle.
+    pub const fn halves() -> Self {
+        Layout {
+            compartments: 2,
+            sharing: Sharing::All,
+        }
+    }
+
+    pub fn new(compartments: usize, sharing: Sharing) -> Result<Self, &'static str> {
+        match compartments {
+            0 => Err("NoCompartments"),
+            n if n > MAX_COMPARTMENTS => Err("TooManyCompartments"),
+            _ => Ok(Layout {
+                compartments,
+                sharing,
+            }),
+        }
+    }
+
+    pub fn compartments(&self) -> usize {
+        self.compartments
+    }
+
+    pub fn sharing(&self) -> Sharing {
+        self.sharing
+    }
+
+    /// Splits the line into its compartments, or returns `None` when its
+    /// length is not a multiple of the compartment count.
+    ///
+    /// The line must be ASCII.
+    pub fn split<'a>(&self, line: &'a str) -> Option<impl Iterator<Item = &'a str>> {
+        if !line.len().is_multiple_of(self.compartments) {
+            return None;
+        }
+
+        let size = line.len() / self.compartments;
+        Some((0..self.compartments).map(move |i| &line[i * size..(i + 1) * size]))
+    }
+
+    /// The item types the compartments have in common, found in one pass.
+    pub fn shared(&self, compartments: &[ItemSet]) -> ItemSet {
+        let mut seen = ItemSet::empty();
+        let mut twice = ItemSet::empty();
+        let mut every: Option<ItemSet> = None;
+        for &compartment in compartments {
+            twice = twice | (seen & compartment);
+            seen = seen | compartment;
+            every = Some(every.map_or(compartment, |every| every & compartment));
+        }
+
+        match self.sharing {
+            Sharing::All => every.unwrap_or_default(),
+            Sharing::AnyTwo => twice,
+        }
+    }
+}
+
+impl Default for Layout {
+    fn default() -> Self {
+        Layout::halves()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::alphabet::Alphabet;
+
+    fn split(layout: &Layout, line: &'static str) -> Option<Vec<&'static str>> {
+        layout.split(line).map(Iterator::collect)
+    }
+
+    fn item_sets(compartments: &[&str]) -> Vec<ItemSet> {
+        let letters = Alphabet::letters();
+        compartments
+            .iter()
+            .map(|c| letters.item_set(c).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_split() -> Result<(), &'static str> {
+        let halves = Layout::halves();
+        assert_eq!(split(&halves, "abcd"), Some(vec!["ab", "cd"]));
+        assert_eq!(split(&halves, ""), Some(vec!["", ""]));
+        assert_eq!(split(&halves, "abc"), None);
+
+        let thirds = Layout::new(3, Sharing::All)?;
+        assert_eq!(split(&thirds, "abcdef"), Some(vec!["ab", "cd", "ef"]));
+        assert_eq!(split(&thirds, "abcd"), None);
+
+        assert_eq!(Layout::new(0, Sharing::All), Err("NoCompartments"));
+        assert!(Layout::new(MAX_COMPARTMENTS, Sharing::All).is_ok());
+        assert_eq!(
+            Layout::new(MAX_COMPARTMENTS + 1, Sharing::All),
+            Err("TooManyCompartments")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_shared() -> Result<(), &'static str> {
+        let compartments = item_sets(&["abc", "bcd", "cde", "cxy"]);
+        let all = Layout::new(4, Sharing::All)?;
+        let any_two = Layout::new(4, Sharing::AnyTwo)?;
+
+        assert_eq!(all.shared(&compartments), item_sets(&["c"])[0]);
+        assert_eq!(any_two.shared(&compartments), item_sets(&["bcd"])[0]);
+        assert_eq!(all.shared(&[]), ItemSet::empty());
+
+        // With two compartments, both ways of sharing agree.
+        let halves = item_sets(&["abc", "cbz"]);
+        let two = Layout::new(2, Sharing::AnyTwo)?;
+        assert_eq!(two.shared(&halves), Layout::halves().shared(&halves));
+        Ok(())
+    }
+}
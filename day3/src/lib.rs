@@ -0,0 +1,26 @@
+pub mod alphabet;
+pub mod grouping;
+pub mod item_set;
+pub mod layout;
+pub mod parallel;
+pub mod planner;
+pub mod rucksack;
+pub mod stats;
+
+pub use alphabet::Alphabet;
+pub use item_set::ItemSet;
+pub use layout::{Layout, Sharing};
+pub use parallel::{par_sum_badge_priorities, par_sum_priorities};
+pub use rucksack::{
+    badge_priority, badge_priority_sum, check_groups, find_badge, groups, parse_rucksacks,
+    priority_sum, read_rucksacks, report, sum_badge_priorities, sum_priorities, validate, Common,
+    Rucksack, RucksackError, Violation,
+};
+
+/// The puzzle's alphabet, shared by the tests.
+#[cfg(test)]
+pub(crate) const LETTERS: Alphabet = Alphabet::letters();
+
+/// The puzzle's layout, shared by the tests.
+#[cfg(test)]
+pub(crate) const HALVES: Layout = Layout::halves();
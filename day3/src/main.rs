@@ -67,67 +67,243 @@ If you still want to see it, you can get your puzzle input.
 
 You can also [Share] this puzzle.
 */
+use std::env;
+use std::error::Error;
+use std::io;
+use std::thread;
+use std::time::Instant;
+
+use day3::{grouping, planner, stats};
+use day3::{parse_rucksacks, report, validate, Alphabet, Rucksack, RucksackError};
+use day3::{Layout, Sharing};
+
+fn part1(threads: usize, layout: &Layout, alphabet: &Alphabet) -> Result<u32, RucksackError> {
+    let input = include_str!("../data/input.txt");
+    match threads {
+        1 => day3::sum_priorities(input, layout, alphabet),
+        _ => day3::par_sum_priorities(input, threads, layout, alphabet),
+    }
+}
 
-use std::collections::HashSet;
+fn part2(
+    group_size: usize,
+    threads: usize,
+    layout: &Layout,
+    alphabet: &Alphabet,
+) -> Result<u32, RucksackError> {
+    let input = include_str!("../data/input.txt");
+    match threads {
+        1 => day3::sum_badge_priorities(input, group_size, layout, alphabet),
+        _ => day3::par_sum_badge_priorities(input, group_size, threads, layout, alphabet),
+    }
+}
 
-fn find_common(s1: &str, s2: &str) -> char {
-    let set1: HashSet<char> = s1.chars().collect();
-    let set2: HashSet<char> = s2.chars().collect();
-    let common: Vec<&char> = set1.intersection(&set2).collect();
-    *common[0]
+enum Mode {
+    Answers,
+    Report,
+    Plan,
+    Discover,
+    Stats,
+    Bench(usize),
 }
 
-fn find_badge(s1: &str, s2: &str, s3: &str) -> char {
-    let set1: HashSet<char> = s1.chars().collect();
-    let set2: HashSet<char> = s2.chars().collect();
-    let set3: HashSet<char> = s3.chars().collect();
+struct Options {
+    mode: Mode,
+    group_size: usize,
+    layout: Layout,
+    alphabet: Alphabet,
+    csv: bool,
+    threads: Option<usize>,
+}
 
-    let common_1_and_2: HashSet<char> = set1.intersection(&set2).copied().collect();
-    let common: Vec<&char> = common_1_and_2.intersection(&set3).collect();
+fn parse_args<I>(mut args: I) -> Result<Options, Box<dyn Error>>
+where
+    I: Iterator<Item = String>,
+{
+    let mut options = Options {
+        mode: Mode::Answers,
+        group_size: 3,
+        layout: Layout::halves(),
+        alphabet: Alphabet::letters(),
+        csv: false,
+        threads: None,
+    };
+    let mut compartments = 2;
+    let mut sharing = Sharing::All;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--group-size" => {
+                options.group_size = args.next().ok_or("--group-size needs a value")?.parse()?;
+            }
+            "--report" => options.mode = Mode::Report,
+            "--plan" => options.mode = Mode::Plan,
+            "--discover" => options.mode = Mode::Discover,
+            "--stats" => options.mode = Mode::Stats,
+            "--csv" => options.csv = true,
+            "--compartments" => {
+                compartments = args.next().ok_or("--compartments needs a value")?.parse()?;
+            }
+            "--any-two" => sharing = Sharing::AnyTwo,
+            "--threads" => {
+                options.threads = Some(args.next().ok_or("--threads needs a value")?.parse()?);
+            }
+            "--bench" => {
+                let copies = args.next().ok_or("--bench needs a value")?.parse()?;
+                options.mode = Mode::Bench(copies);
+            }
+            "--alphabet" => {
+                options.alphabet = Alphabet::new(&args.next().ok_or("--alphabet needs a value")?)?;
+            }
+            _ => return Err(format!("unknown argument {}", arg).into()),
+        }
+    }
+    options.layout = Layout::new(compartments, sharing)?;
 
-    *common[0]
+    Ok(options)
 }
 
-fn get_priority(c: char) -> u32 {
-    if c.is_ascii_lowercase() {
-        1 + c as u32 - 'a' as u32
-    } else {
-        27 + c as u32 - 'A' as u32
+/// Describes the moves that repack one rucksack.
+fn describe_plan(line: &str, alphabet: &Alphabet) -> String {
+    match planner::plan(line, alphabet) {
+        Some(moves) if moves.is_empty() => "already disjoint".to_string(),
+        Some(moves) => {
+            let moves: Vec<String> = moves.iter().map(|m| m.to_string()).collect();
+            moves.join(", ")
+        }
+        None => "cannot be repacked".to_string(),
     }
 }
 
-fn part1() -> u32 {
-    let input = include_str!("../data/input.txt").lines();
+fn print_plans(input: &str, alphabet: &Alphabet) {
+    for (i, line) in input.lines().enumerate() {
+        println!("line {}: {}", i + 1, describe_plan(line, alphabet));
+    }
+}
 
-    let mut total = 0;
-    for line in input {
-        let (s1, s2) = line.split_at(line.len() / 2);
-        let common = find_common(s1, s2);
-        let priority = get_priority(common);
-        total += priority;
+fn print_discovered_groups(
+    rucksacks: &[Rucksack],
+    group_size: usize,
+    alphabet: &Alphabet,
+) -> Result<(), Box<dyn Error>> {
+    let groups = grouping::discover_badges(rucksacks, group_size, alphabet)?
+        .ok_or("no grouping shares exactly one item per group")?;
+
+    for group in &groups {
+        let lines: Vec<String> = group.members.iter().map(|i| (i + 1).to_string()).collect();
+        println!(
+            "lines {}: badge {} ({})",
+            lines.join(", "),
+            group.badge,
+            group.priority
+        );
     }
+    println!("Badge priorities {}", grouping::badge_priority_sum(&groups));
 
-    total
+    Ok(())
 }
 
-fn part2() -> u32 {
-    let mut input = include_str!("../data/input.txt").lines();
-    let mut total = 0;
-    while let Some(s1) = input.next() {
-        let s2 = input.next().unwrap();
-        let s3 = input.next().unwrap();
-
-        let badge = find_badge(s1, s2, s3);
-        let priority = get_priority(badge);
-        total += priority;
+/// Times the sequential and parallel sums over `copies` back to back copies
+/// of the input, which keeps every group of the original intact.
+fn benchmark(
+    input: &str,
+    copies: usize,
+    group_size: usize,
+    threads: usize,
+    layout: &Layout,
+    alphabet: &Alphabet,
+) -> Result<(), Box<dyn Error>> {
+    let manifest = format!("{}\n", input.trim_end()).repeat(copies);
+
+    let start = Instant::now();
+    let expected = (
+        day3::sum_priorities(&manifest, layout, alphabet)?,
+        day3::sum_badge_priorities(&manifest, group_size, layout, alphabet)?,
+    );
+    let sequential = start.elapsed();
+
+    let start = Instant::now();
+    let actual = (
+        day3::par_sum_priorities(&manifest, threads, layout, alphabet)?,
+        day3::par_sum_badge_priorities(&manifest, group_size, threads, layout, alphabet)?,
+    );
+    let parallel = start.elapsed();
+
+    if actual != expected {
+        return Err(format!("parallel summed {:?}, expected {:?}", actual, expected).into());
     }
 
-    total
+    println!("{} rucksacks summed {:?}", manifest.lines().count(), actual);
+    println!("sequential {:?}", sequential);
+    println!("parallel on {} threads {:?}", threads, parallel);
+
+    Ok(())
 }
 
-fn main() {
-    println!("Part1 answer {}", part1());
-    println!("Part2 answer {}", part2());
+fn main() -> Result<(), Box<dyn Error>> {
+    let options = parse_args(env::args().skip(1))?;
+    let layout = &options.layout;
+    let alphabet = &options.alphabet;
+    let input = include_str!("../data/input.txt");
+
+    let errors = validate(input, layout, alphabet);
+    if !errors.is_empty() {
+        for error in &errors {
+            println!("{}", error);
+        }
+        return Err(format!("{} invalid rucksacks", errors.len()).into());
+    }
+
+    match options.mode {
+        Mode::Answers => {
+            println!(
+                "Part1 answer {}",
+                part1(options.threads.unwrap_or(1), layout, alphabet)?
+            );
+            println!(
+                "Part2 answer {}",
+                part2(
+                    options.group_size,
+                    options.threads.unwrap_or(1),
+                    layout,
+                    alphabet
+                )?
+            );
+        }
+        Mode::Report => {
+            let rucksacks = parse_rucksacks(input, layout, alphabet)?;
+            for violation in report(&rucksacks, options.group_size, alphabet)? {
+                println!("{}", violation);
+            }
+        }
+        Mode::Plan => {
+            if layout.compartments() != 2 {
+                return Err("plans need exactly two compartments".into());
+            }
+            print_plans(input, alphabet);
+        }
+        Mode::Discover => {
+            let rucksacks = parse_rucksacks(input, layout, alphabet)?;
+            print_discovered_groups(&rucksacks, options.group_size, alphabet)?;
+        }
+        Mode::Stats => {
+            let freq =
+                stats::Frequencies::from_rucksacks(&parse_rucksacks(input, layout, alphabet)?);
+            match options.csv {
+                true => stats::write_csv(&freq, alphabet, io::stdout())?,
+                false => stats::write_table(&freq, alphabet, io::stdout())?,
+            }
+        }
+        Mode::Bench(copies) => {
+            // Without --threads, use every available core.
+            let threads = options
+                .threads
+                .unwrap_or_else(|| thread::available_parallelism().map_or(1, usize::from));
+            benchmark(input, copies, options.group_size, threads, layout, alphabet)?;
+        }
+    }
+
+    Ok(())
 }
 
 #[cfg(test)]
@@ -135,63 +311,50 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_find_common() {
-        assert_eq!(find_common("vJrwpWtwJgWr", "hcsFMMfFFhFp"), 'p');
-    }
-
-    #[test]
-    fn test_get_priority() {
-        assert_eq!(get_priority('p'), 16);
+    fn test_answers() {
+        let letters = Alphabet::letters();
+        let halves = Layout::halves();
+        for threads in [1, 4] {
+            assert_eq!(part1(threads, &halves, &letters), Ok(7997));
+            assert_eq!(part2(3, threads, &halves, &letters), Ok(2545));
+        }
     }
 
     #[test]
-    fn test_sum_priorities() {
-        let input = include_str!("../data/input_test.txt").lines();
-
-        let mut total = 0;
-        for line in input {
-            let (s1, s2) = line.split_at(line.len() / 2);
-            let common = find_common(s1, s2);
-            let priority = get_priority(common);
-            total += priority;
-        }
-
-        assert_eq!(total, 157);
+    fn test_describe_plan() {
+        let letters = Alphabet::letters();
+        assert_eq!(describe_plan("abcd", &letters), "already disjoint");
+        assert_eq!(describe_plan("aaab", &letters), "cannot be repacked");
+        assert!(!describe_plan("abca", &letters).is_empty());
     }
 
     #[test]
-    fn test_find_badge() {
+    fn test_parse_args() -> Result<(), Box<dyn Error>> {
+        let args = ["--group-size", "4"].map(String::from);
+        assert_eq!(parse_args(args.into_iter())?.group_size, 4);
+        assert_eq!(parse_args(std::iter::empty())?.group_size, 3);
+        assert_eq!(parse_args(std::iter::empty())?.threads, None);
+
+        let args = ["--threads", "8", "--bench", "10"].map(String::from);
+        let options = parse_args(args.into_iter())?;
+        assert_eq!(options.threads, Some(8));
+        assert!(matches!(options.mode, Mode::Bench(10)));
+        assert!(parse_args(["--group-size"].map(String::from).into_iter()).is_err());
+
+        let args = ["--alphabet", "xyz"].map(String::from);
         assert_eq!(
-            find_badge(
-                "vJrwpWtwJgWrhcsFMMfFFhFp",
-                "jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL",
-                "PmmdzqPrVvPwwTWBwg"
-            ),
-            'r'
+            parse_args(args.into_iter())?.alphabet.priority('z'),
+            Some(3)
         );
+        let args = ["--compartments", "4", "--any-two"].map(String::from);
         assert_eq!(
-            find_badge(
-                "wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn",
-                "ttgJtRGJQctTZtZT",
-                "CrZsJsPPZsGzwwsLwLmpwMDw"
-            ),
-            'Z'
+            parse_args(args.into_iter())?.layout,
+            Layout::new(4, Sharing::AnyTwo)?
         );
-    }
-
-    #[test]
-    fn test_sum_badge_priorities() {
-        let mut input = include_str!("../data/input_test.txt").lines();
-        let mut total = 0;
-        while let Some(s1) = input.next() {
-            let s2 = input.next().unwrap();
-            let s3 = input.next().unwrap();
-
-            let badge = find_badge(s1, s2, s3);
-            let priority = get_priority(badge);
-            total += priority;
-        }
+        assert!(parse_args(["--compartments", "0"].map(String::from).into_iter()).is_err());
 
-        assert_eq!(total, 70);
+        let args = ["--alphabet", "xx"].map(String::from);
+        assert!(parse_args(args.into_iter()).is_err());
+        Ok(())
     }
 }
@@ -0,0 +1,165 @@
+use std::thread;
+
+use crate::alphabet::Alphabet;
+use crate::layout::Layout;
+use crate::rucksack::{badge_priority_sum, check_groups, priority_sum, Rucksack, RucksackError};
+
+/// Parses and sums the lines on up to `threads` threads, giving each a run of
+/// whole `unit`s of lines.
+///
+/// Errors are reported as the sequential path would: the first line that is
+/// not a rucksack wins over any error found while summing.
+fn sum_chunks<F>(
+    lines: &[&str],
+    unit: usize,
+    threads: usize,
+    layout: &Layout,
+    alphabet: &Alphabet,
+    sum: F,
+) -> Result<u32, RucksackError>
+where
+    F: Fn(&[Rucksack]) -> Result<u32, RucksackError> + Sync,
+{
+    let units = lines.len().div_ceil(unit);
+    let chunk_len = units.div_ceil(threads.max(1)).max(1) * unit;
+
+    let results: Vec<Result<Result<u32, RucksackError>, RucksackError>> = thread::scope(|scope| {
+        let handles: Vec<_> = lines
+            .chunks(chunk_len)
+            .enumerate()
+            .map(|(c, chunk)| {
+                let sum = &sum;
+                scope.spawn(move || {
+                    let offset = c * chunk_len;
+                    let rucksacks = chunk
+                        .iter()
+                        .enumerate()
+                        .map(|(i, line)| Rucksack::parse(line, offset + i + 1, layout, alphabet))
+                        .collect::<Result<Vec<_>, _>>()?;
+                    Ok(sum(&rucksacks))
+                })
+            })
+            .collect();
+
+        handles
+            .into_iter()
+            .map(|h| h.join().expect("summing thread panicked"))
+            .collect()
+    });
+
+    let mut total = 0;
+    let mut first_error = None;
+    for result in results {
+        match result? {
+            Ok(sum) => total += sum,
+            Err(e) => {
+                first_error.get_or_insert(e);
+            }
+        }
+    }
+
+    match first_error {
+        Some(e) => Err(e),
+        None => Ok(total),
+    }
+}
+
+/// Same as `sum_priorities`, split across up to `threads` threads.
+pub fn par_sum_priorities(
+    input: &str,
+    threads: usize,
+    layout: &Layout,
+    alphabet: &Alphabet,
+) -> Result<u32, RucksackError> {
+    let lines: Vec<&str> = input.lines().collect();
+    sum_chunks(&lines, 1, threads, layout, alphabet, |rucksacks| {
+        priority_sum(rucksacks, alphabet)
+    })
+}
+
+/// Same as `sum_badge_priorities`, split across up to `threads` threads
+/// without breaking up any group.
+pub fn par_sum_badge_priorities(
+    input: &str,
+    group_size: usize,
+    threads: usize,
+    layout: &Layout,
+    alphabet: &Alphabet,
+) -> Result<u32, RucksackError> {
+    let lines: Vec<&str> = input.lines().collect();
+
+    // Bad group sizes only surface once every line has parsed, so the lines
+    // are still checked but not summed.
+    let aligned = check_groups(lines.len(), group_size);
+    let unit = if aligned.is_ok() { group_size } else { 1 };
+    let total = sum_chunks(
+        &lines,
+        unit,
+        threads,
+        layout,
+        alphabet,
+        |rucksacks| match aligned {
+            Ok(()) => badge_priority_sum(rucksacks, group_size, alphabet),
+            Err(_) => Ok(0),
+        },
+    )?;
+    aligned?;
+
+    Ok(total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rucksack::{sum_badge_priorities, sum_priorities};
+    use crate::{HALVES, LETTERS};
+
+    #[test]
+    fn test_matches_sequential() {
+        let input = include_str!("../data/input.txt");
+        for threads in [0, 1, 2, 3, 7, 64, 1000] {
+            assert_eq!(
+                par_sum_priorities(input, threads, &HALVES, &LETTERS),
+                sum_priorities(input, &HALVES, &LETTERS)
+            );
+            for group_size in [1, 2, 3, 5, 6] {
+                assert_eq!(
+                    par_sum_badge_priorities(input, group_size, threads, &HALVES, &LETTERS),
+                    sum_badge_priorities(input, group_size, &HALVES, &LETTERS),
+                    "{} threads, groups of {}",
+                    threads,
+                    group_size
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_errors_match_sequential() {
+        let inputs = [
+            "",
+            "abab\nabcd\n",
+            "abcd\nabab\nab1b\n",
+            "vJrwpWtwJgWrhcsFMMfFFhFp\nabc\nabcd\n",
+            include_str!("../data/input_test.txt"),
+        ];
+        for input in inputs {
+            for threads in [1, 2, 3] {
+                assert_eq!(
+                    par_sum_priorities(input, threads, &HALVES, &LETTERS),
+                    sum_priorities(input, &HALVES, &LETTERS)
+                );
+                for group_size in [0, 1, 2, 3, 4] {
+                    assert_eq!(
+                        par_sum_badge_priorities(input, group_size, threads, &HALVES, &LETTERS),
+                        sum_badge_priorities(input, group_size, &HALVES, &LETTERS),
+                        "{:?}, {} threads, groups of {}",
+                        input,
+                        threads,
+                        group_size
+                    );
+                }
+            }
+        }
+    }
+}
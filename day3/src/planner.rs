@@ -0,0 +1,210 @@
+use std::fmt;
+
+use crate::alphabet::Alphabet;
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Compartment {
+    First,
+    Second,
+}
+
+/// Moving every `count` items of one type out of a compartment into the
+/// other.
+#[derive(Debug, Eq, PartialEq)]
+pub struct Move {
+    pub item: char,
+    pub count: usize,
+    pub from: Compartment,
+}
+
+impl fmt::Display for Move {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (from, to) = match self.from {
+            Compartment::First => ("first", "second"),
+            Compartment::Second => ("second", "first"),
+        };
+        write!(
+            f,
+            "move {} {} from {} to {}",
+            self.count, self.item, from, to
+        )
+    }
+}
+
+/// Finds the fewest item moves that leave no item type in both compartments
+/// while keeping the compartments the same size, or `None` when no such
+/// arrangement exists.
+///
+/// Each item type ends up wholly in one compartment, so the plan is the
+/// cheapest choice of types for the first compartment whose counts add up to
+/// half of the rucksack, found by dynamic programming over the types.
+///
+/// The line must already be a valid rucksack for the alphabet.
+pub fn plan(line: &str, alphabet: &Alphabet) -> Option<Vec<Move>> {
+    let half = line.len() / 2;
+
+    let mut counts = [[0usize; 2]; 64];
+    for (i, c) in line.chars().enumerate() {
+        let priority = alphabet.priority(c)? as usize;
+        counts[priority][usize::from(i >= half)] += 1;
+    }
+    let types: Vec<usize> = (0..64)
+        .filter(|&p| counts[p][0] + counts[p][1] > 0)
+        .collect();
+
+    // best[s] is the fewest moves that put s items in the first
+    // compartment, and placed[k][s] records whether type k went there.
+    let mut best: Vec<Option<usize>> = vec![None; half + 1];
+    best[0] = Some(0);
+    let mut placed: Vec<Vec<bool>> = Vec::with_capacity(types.len());
+
+    for &p in &types {
+        let [first, second] = counts[p];
+        let mut next: Vec<Option<usize>> = vec![None; half + 1];
+        let mut choice = vec![false; half + 1];
+
+        for (size, cost) in best.iter().enumerate() {
+            let Some(cost) = cost else { continue };
+
+            let mut relax = |size: usize, cost: usize, in_first: bool| {
+                if next[size].is_none_or(|c| cost < c) {
+                    next[size] = Some(cost);
+                    choice[size] = in_first;
+                }
+            };
+
+            relax(size, cost + first, false);
+            if size + first + second <= half {
+                relax(size + first + second, cost + second, true);
+            }
+        }
+
+        best = next;
+        placed.push(choice);
+    }
+
+    best[half]?;
+
+    let mut moves = Vec::new();
+    let mut size = half;
+    for (k, &p) in types.iter().enumerate().rev() {
+        let [first, second] = counts[p];
+        let item = alphabet.item(p as u32)?;
+
+        if placed[k][size] {
+            size -= first + second;
+            if second > 0 {
+                moves.push(Move {
+                    item,
+                    count: second,
+                    from: Compartment::Second,
+                });
+            }
+        } else if first > 0 {
+            moves.push(Move {
+                item,
+                count: first,
+                from: Compartment::First,
+            });
+        }
+    }
+    moves.reverse();
+
+    Some(moves)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::LETTERS;
+
+    /// Applies the moves and checks that the compartments end up disjoint
+    /// and equally sized.
+    fn check(line: &str, moves: &[Move]) {
+        let (s1, s2) = line.split_at(line.len() / 2);
+        let mut first: Vec<char> = s1.chars().collect();
+        let mut second: Vec<char> = s2.chars().collect();
+
+        for m in moves {
+            let (from, to) = match m.from {
+                Compartment::First => (&mut first, &mut second),
+                Compartment::Second => (&mut second, &mut first),
+            };
+            assert_eq!(from.iter().filter(|&&c| c == m.item).count(), m.count);
+            from.retain(|&c| c != m.item);
+            to.extend(std::iter::repeat_n(m.item, m.count));
+        }
+
+        assert_eq!(first.len(), second.len());
+        assert!(first.iter().all(|c| !second.contains(c)), "{}", line);
+    }
+
+    fn total(moves: &[Move]) -> usize {
+        moves.iter().map(|m| m.count).sum()
+    }
+
+    #[test]
+    fn test_plan_example() {
+        let line = "vJrwpWtwJgWrhcsFMMfFFhFp";
+        let moves = plan(line, &LETTERS).unwrap();
+        check(line, &moves);
+        assert_eq!(total(&moves), 2);
+        assert!(moves.iter().any(|m| m.item == 'p'));
+    }
+
+    #[test]
+    fn test_plan_test_input() {
+        for line in include_str!("../data/input_test.txt").lines() {
+            let moves = plan(line, &LETTERS).unwrap();
+            check(line, &moves);
+        }
+    }
+
+    #[test]
+    fn test_plan_already_disjoint() {
+        assert_eq!(plan("abcd", &LETTERS), Some(vec![]));
+    }
+
+    #[test]
+    fn test_plan_minimal() {
+        // Three shared types cost a move each, and the unshared d or e has
+        // to follow whichever way keeps the sizes even.
+        let moves = plan("abcdabce", &LETTERS).unwrap();
+        check("abcdabce", &moves);
+        assert_eq!(total(&moves), 4);
+
+        let moves = plan("aaaaababbbbb", &LETTERS).unwrap();
+        assert_eq!(
+            moves,
+            vec![
+                Move {
+                    item: 'a',
+                    count: 1,
+                    from: Compartment::Second
+                },
+                Move {
+                    item: 'b',
+                    count: 1,
+                    from: Compartment::First
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_plan_infeasible() {
+        assert_eq!(plan("aaab", &LETTERS), None);
+        assert_eq!(plan("abcabc", &LETTERS), None);
+        assert_eq!(plan("aaaabbbabbbb", &LETTERS), None);
+    }
+
+    #[test]
+    fn test_move_display() {
+        let m = Move {
+            item: 'p',
+            count: 2,
+            from: Compartment::First,
+        };
+        assert_eq!(m.to_string(), "move 2 p from first to second");
+    }
+}
@@ -0,0 +1,658 @@
+use std::error::Error;
+use std::fmt;
+use std::io::{self, BufRead};
+use std::ops::BitAnd;
+use std::slice::Chunks;
+
+use crate::alphabet::Alphabet;
+use crate::item_set::ItemSet;
+use crate::layout::{Layout, MAX_COMPARTMENTS};
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum RucksackError {
+    InvalidGroupSize,
+    IncompleteGroup {
+        line_number: usize,
+        members: usize,
+        group_size: usize,
+    },
+    NoCommonItem {
+        line_number: usize,
+    },
+    AmbiguousCommonItem {
+        line_number: usize,
+        items: Vec<char>,
+    },
+    UnevenLength {
+        line_number: usize,
+        len: usize,
+        compartments: usize,
+    },
+    InvalidItem {
+        line_number: usize,
+        item: char,
+    },
+    Io {
+        line_number: usize,
+        kind: io::ErrorKind,
+    },
+}
+
+impl fmt::Display for RucksackError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RucksackError::InvalidGroupSize => write!(f, "group size must be at least 1"),
+            RucksackError::IncompleteGroup {
+                line_number,
+                members,
+                group_size,
+            } => write!(
+                f,
+                "line {}: group has {} of {} rucksacks",
+                line_number, members, group_size
+            ),
+            RucksackError::NoCommonItem { line_number } => {
+                write!(f, "line {}: no common item", line_number)
+            }
+            RucksackError::AmbiguousCommonItem { line_number, items } => {
+                write!(f, "line {}: several common items {:?}", line_number, items)
+            }
+            RucksackError::UnevenLength {
+                line_number,
+                len,
+                compartments,
+            } => write!(
+                f,
+                "line {}: {} items cannot be split into {} equal compartments",
+                line_number, len, compartments
+            ),
+            RucksackError::InvalidItem { line_number, item } => {
+                write!(f, "line {}: invalid item {:?}", line_number, item)
+            }
+            RucksackError::Io { line_number, kind } => {
+                write!(f, "line {}: {}", line_number, kind)
+            }
+        }
+    }
+}
+
+impl Error for RucksackError {}
+
+/// The item types shared by two compartments or by a group of rucksacks,
+/// where the puzzle expects exactly one.
+#[derive(Debug, Eq, PartialEq)]
+pub enum Common {
+    Exactly(char),
+    Absent,
+    Ambiguous(Vec<char>),
+}
+
+impl Common {
+    pub fn from_set(set: ItemSet, alphabet: &Alphabet) -> Self {
+        let item = |p| alphabet.item(p).unwrap_or(char::REPLACEMENT_CHARACTER);
+        let mut items = set.iter();
+        match items.len() {
+            0 => Common::Absent,
+            1 => Common::Exactly(item(items.next().unwrap())),
+            _ => Common::Ambiguous(items.map(item).collect()),
+        }
+    }
+
+    pub fn into_result(self, line_number: usize) -> Result<char, RucksackError> {
+        match self {
+            Common::Exactly(item) => Ok(item),
+            Common::Absent => Err(RucksackError::NoCommonItem { line_number }),
+            Common::Ambiguous(items) => {
+                Err(RucksackError::AmbiguousCommonItem { line_number, items })
+            }
+        }
+    }
+}
+
+/// A rucksack's items, split into its equally sized compartments.
+#[derive(Debug)]
+pub struct Rucksack {
+    line_number: usize,
+    /// Only the first `len` are in use.
+    compartments: [ItemSet; MAX_COMPARTMENTS],
+    len: usize,
+    shared: ItemSet,
+}
+
+impl Rucksack {
+    /// ```
+    /// # use day3::{Alphabet, Common, Layout, Rucksack, Sharing};
+    ///
+    /// let letters = Alphabet::letters();
+    /// let line = "vJrwpWtwJgWrhcsFMMfFFhFp";
+    /// let rucksack = Rucksack::parse(line, 1, &Layout::halves(), &letters).unwrap();
+    ///
+    /// assert_eq!(rucksack.common(&letters), Common::Exactly('p'));
+    /// assert_eq!(rucksack.priority(&letters), Ok(16));
+    ///
+    /// let quarters = Layout::new(4, Sharing::AnyTwo).unwrap();
+    /// let rucksack = Rucksack::parse(line, 1, &quarters, &letters).unwrap();
+    ///
+    /// assert_eq!(rucksack.common(&letters), Common::Ambiguous(vec!['h', 'p', 'r', 'w', 'F', 'J', 'W']));
+    /// ```
+    pub fn parse(
+        line: &str,
+        line_number: usize,
+        layout: &Layout,
+        alphabet: &Alphabet,
+    ) -> Result<Self, RucksackError> {
+        if let Some(item) = line.chars().find(|&c| alphabet.priority(c).is_none()) {
+            return Err(RucksackError::InvalidItem { line_number, item });
+        }
+
+        // Every item is ASCII, so the length in bytes is the number of items.
+        let Some(parts) = layout.split(line) else {
+            return Err(RucksackError::UnevenLength {
+                line_number,
+                len: line.len(),
+                compartments: layout.compartments(),
+            });
+        };
+
+        // Every item was checked above, so the item sets cannot fail.
+        let mut compartments = [ItemSet::empty(); MAX_COMPARTMENTS];
+        for (compartment, part) in compartments.iter_mut().zip(parts) {
+            *compartment = alphabet.item_set(part).unwrap_or_default();
+        }
+        let len = layout.compartments();
+        let shared = layout.shared(&compartments[..len]);
+        Ok(Rucksack {
+            line_number,
+            compartments,
+            len,
+            shared,
+        })
+    }
+
+    pub fn line_number(&self) -> usize {
+        self.line_number
+    }
+
+    pub fn compartments(&self) -> &[ItemSet] {
+        &self.compartments[..self.len]
+    }
+
+    pub fn items(&self) -> ItemSet {
+        self.compartments()
+            .iter()
+            .fold(ItemSet::empty(), |items, &compartment| items | compartment)
+    }
+
+    /// The item types the compartments have in common, as the layout it was
+    /// parsed with counts them.
+    pub fn shared(&self) -> ItemSet {
+        self.shared
+    }
+
+    pub fn common(&self, alphabet: &Alphabet) -> Common {
+        Common::from_set(self.shared, alphabet)
+    }
+
+    /// The priority of the one item type the compartments share.
+    pub fn priority(&self, alphabet: &Alphabet) -> Result<u32, RucksackError> {
+        let common = self.common(alphabet).into_result(self.line_number)?;
+        Ok(alphabet.priority(common).unwrap_or_default())
+    }
+}
+
+pub fn parse_rucksacks(
+    input: &str,
+    layout: &Layout,
+    alphabet: &Alphabet,
+) -> Result<Vec<Rucksack>, RucksackError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| Rucksack::parse(line, i + 1, layout, alphabet))
+        .collect()
+}
+
+/// ```
+/// # use day3::{read_rucksacks, Alphabet, Layout};
+///
+/// let input = "vJrwpWtwJgWrhcsFMMfFFhFp\njqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL\n";
+/// let rucksacks =
+///     read_rucksacks(input.as_bytes(), &Layout::halves(), &Alphabet::letters()).unwrap();
+///
+/// assert_eq!(rucksacks.len(), 2);
+/// assert_eq!(rucksacks[1].line_number(), 2);
+/// ```
+pub fn read_rucksacks<R: BufRead>(
+    reader: R,
+    layout: &Layout,
+    alphabet: &Alphabet,
+) -> Result<Vec<Rucksack>, RucksackError> {
+    reader
+        .lines()
+        .enumerate()
+        .map(|(i, line)| {
+            let line = line.map_err(|e| RucksackError::Io {
+                line_number: i + 1,
+                kind: e.kind(),
+            })?;
+            Rucksack::parse(&line, i + 1, layout, alphabet)
+        })
+        .collect()
+}
+
+/// Reports every line that is not a valid rucksack.
+pub fn validate(input: &str, layout: &Layout, alphabet: &Alphabet) -> Vec<RucksackError> {
+    input
+        .lines()
+        .enumerate()
+        .filter_map(|(i, line)| Rucksack::parse(line, i + 1, layout, alphabet).err())
+        .collect()
+}
+
+pub fn find_badge<'a, I>(group: I, alphabet: &Alphabet) -> Common
+where
+    I: IntoIterator<Item = &'a Rucksack>,
+{
+    let common = group
+        .into_iter()
+        .map(Rucksack::items)
+        .reduce(BitAnd::bitand);
+    Common::from_set(common.unwrap_or_default(), alphabet)
+}
+
+/// The priority of the one item type carried by every member of the group,
+/// with errors numbered by the group's first line.
+pub fn badge_priority(group: &[Rucksack], alphabet: &Alphabet) -> Result<u32, RucksackError> {
+    let line_number = group.first().map_or(0, Rucksack::line_number);
+    let badge = find_badge(group, alphabet).into_result(line_number)?;
+    Ok(alphabet.priority(badge).unwrap_or_default())
+}
+
+/// Checks that `count` lines split into full groups of `group_size`.
+pub fn check_groups(count: usize, group_size: usize) -> Result<(), RucksackError> {
+    if group_size == 0 {
+        return Err(RucksackError::InvalidGroupSize);
+    }
+
+    match count % group_size {
+        0 => Ok(()),
+        trailing => Err(RucksackError::IncompleteGroup {
+            line_number: count - trailing + 1,
+            members: trailing,
+            group_size,
+        }),
+    }
+}
+
+/// Splits the items into groups of `group_size`, rejecting a trailing group
+/// that is not full.
+pub fn groups<T>(items: &[T], group_size: usize) -> Result<Chunks<'_, T>, RucksackError> {
+    check_groups(items.len(), group_size)?;
+    Ok(items.chunks(group_size))
+}
+
+pub fn priority_sum(rucksacks: &[Rucksack], alphabet: &Alphabet) -> Result<u32, RucksackError> {
+    rucksacks.iter().map(|r| r.priority(alphabet)).sum()
+}
+
+pub fn badge_priority_sum(
+    rucksacks: &[Rucksack],
+    group_size: usize,
+    alphabet: &Alphabet,
+) -> Result<u32, RucksackError> {
+    groups(rucksacks, group_size)?
+        .map(|group| badge_priority(group, alphabet))
+        .sum()
+}
+
+/// ```
+/// # use day3::{sum_priorities, Alphabet, Layout};
+///
+/// let input = "vJrwpWtwJgWrhcsFMMfFFhFp\njqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL\n";
+/// let letters = Alphabet::letters();
+///
+/// assert_eq!(sum_priorities(input, &Layout::halves(), &letters), Ok(16 + 38));
+/// ```
+pub fn sum_priorities(
+    input: &str,
+    layout: &Layout,
+    alphabet: &Alphabet,
+) -> Result<u32, RucksackError> {
+    priority_sum(&parse_rucksacks(input, layout, alphabet)?, alphabet)
+}
+
+/// ```
+/// # use day3::{sum_badge_priorities, Alphabet, Layout};
+///
+/// let input = "vJrwpWtwJgWrhcsFMMfFFhFp\n\
+///              jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL\n\
+///              PmmdzqPrVvPwwTWBwg\n";
+/// let letters = Alphabet::letters();
+///
+/// assert_eq!(sum_badge_priorities(input, 3, &Layout::halves(), &letters), Ok(18));
+/// ```
+pub fn sum_badge_priorities(
+    input: &str,
+    group_size: usize,
+    layout: &Layout,
+    alphabet: &Alphabet,
+) -> Result<u32, RucksackError> {
+    let rucksacks = parse_rucksacks(input, layout, alphabet)?;
+    badge_priority_sum(&rucksacks, group_size, alphabet)
+}
+
+/// A rucksack or group that does not share exactly one item type, numbered
+/// by its first line.
+#[derive(Debug, Eq, PartialEq)]
+pub struct Violation {
+    pub line_number: usize,
+    pub group: bool,
+    pub common: Common,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let subject = if self.group { "group" } else { "rucksack" };
+        match &self.common {
+            Common::Exactly(item) => {
+                write!(f, "line {}: {} shares {}", self.line_number, subject, item)
+            }
+            Common::Absent => write!(f, "line {}: {} shares nothing", self.line_number, subject),
+            Common::Ambiguous(items) => {
+                let items: String = items.iter().collect();
+                write!(f, "line {}: {} shares {}", self.line_number, subject, items)
+            }
+        }
+    }
+}
+
+pub fn report(
+    rucksacks: &[Rucksack],
+    group_size: usize,
+    alphabet: &Alphabet,
+) -> Result<Vec<Violation>, RucksackError> {
+    let mut violations: Vec<Violation> = rucksacks
+        .iter()
+        .map(|rucksack| Violation {
+            line_number: rucksack.line_number,
+            group: false,
+            common: rucksack.common(alphabet),
+        })
+        .collect();
+
+    violations.extend(groups(rucksacks, group_size)?.map(|group| Violation {
+        line_number: group[0].line_number,
+        group: true,
+        common: find_badge(group, alphabet),
+    }));
+    violations.retain(|v| !matches!(v.common, Common::Exactly(_)));
+
+    Ok(violations)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layout::Sharing;
+    use crate::{HALVES, LETTERS};
+
+    fn rucksack(line: &str) -> Rucksack {
+        Rucksack::parse(line, 1, &HALVES, &LETTERS).unwrap()
+    }
+
+    #[test]
+    fn test_common() {
+        assert_eq!(
+            rucksack("vJrwpWtwJgWrhcsFMMfFFhFp").common(&LETTERS),
+            Common::Exactly('p')
+        );
+        assert_eq!(rucksack("abcdef").common(&LETTERS), Common::Absent);
+        assert_eq!(
+            rucksack("abCddCba").common(&LETTERS),
+            Common::Ambiguous(vec!['a', 'b', 'd', 'C'])
+        );
+    }
+
+    #[test]
+    fn test_get_priority() {
+        assert_eq!(LETTERS.priority('p'), Some(16));
+        assert_eq!(
+            rucksack("vJrwpWtwJgWrhcsFMMfFFhFp").priority(&LETTERS),
+            Ok(16)
+        );
+        assert_eq!(
+            Rucksack::parse("abcdef", 4, &HALVES, &LETTERS)
+                .unwrap()
+                .priority(&LETTERS),
+            Err(RucksackError::NoCommonItem { line_number: 4 })
+        );
+    }
+
+    #[test]
+    fn test_parse_rucksack() {
+        let parsed = rucksack("vJrwpWtwJgWrhcsFMMfFFhFp");
+        assert_eq!(
+            parsed.compartments(),
+            [
+                LETTERS.item_set("vJrwpWtwJgWr").unwrap(),
+                LETTERS.item_set("hcsFMMfFFhFp").unwrap()
+            ]
+        );
+        assert_eq!(
+            parsed.items(),
+            LETTERS.item_set("vJrwpWtwJgWrhcsFMMfFFhFp").unwrap()
+        );
+        assert_eq!(parsed.shared(), LETTERS.item_set("p").unwrap());
+
+        assert_eq!(
+            Rucksack::parse("abc", 7, &HALVES, &LETTERS).unwrap_err(),
+            RucksackError::UnevenLength {
+                line_number: 7,
+                len: 3,
+                compartments: 2
+            }
+        );
+        assert_eq!(
+            Rucksack::parse("ab1c", 2, &HALVES, &LETTERS).unwrap_err(),
+            RucksackError::InvalidItem {
+                line_number: 2,
+                item: '1'
+            }
+        );
+        assert_eq!(
+            Rucksack::parse("aé", 3, &HALVES, &LETTERS).unwrap_err(),
+            RucksackError::InvalidItem {
+                line_number: 3,
+                item: 'é'
+            }
+        );
+    }
+
+    #[test]
+    fn test_compartments() -> Result<(), Box<dyn Error>> {
+        let thirds = Layout::new(3, Sharing::All)?;
+        let any_two = Layout::new(3, Sharing::AnyTwo)?;
+
+        let parsed = Rucksack::parse("abacad", 1, &thirds, &LETTERS)?;
+        let sets = ["ab", "ac", "ad"].map(|c| LETTERS.item_set(c).unwrap());
+        assert_eq!(parsed.compartments(), sets);
+        assert_eq!(parsed.shared(), LETTERS.item_set("a").unwrap());
+
+        assert_eq!(sum_priorities("abacad\n", &thirds, &LETTERS), Ok(1));
+        assert_eq!(sum_priorities("abcbde\n", &any_two, &LETTERS), Ok(2));
+        assert_eq!(
+            sum_priorities("abcbde\n", &thirds, &LETTERS),
+            Err(RucksackError::NoCommonItem { line_number: 1 })
+        );
+        assert_eq!(
+            sum_priorities("abacad\nabcd\n", &thirds, &LETTERS),
+            Err(RucksackError::UnevenLength {
+                line_number: 2,
+                len: 4,
+                compartments: 3
+            })
+        );
+
+        // Badges come from every item in the rucksack, however it is split.
+        let input = include_str!("../data/input_test.txt");
+        let whole = Layout::new(1, Sharing::All)?;
+        assert_eq!(sum_badge_priorities(input, 3, &whole, &LETTERS), Ok(70));
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_rucksacks() -> Result<(), RucksackError> {
+        let input = include_str!("../data/input_test.txt");
+        let rucksacks = read_rucksacks(input.as_bytes(), &HALVES, &LETTERS)?;
+        assert_eq!(priority_sum(&rucksacks, &LETTERS), Ok(157));
+        assert_eq!(badge_priority_sum(&rucksacks, 3, &LETTERS), Ok(70));
+
+        assert_eq!(
+            read_rucksacks(&b"abab\n\xff\xfe\n"[..], &HALVES, &LETTERS).unwrap_err(),
+            RucksackError::Io {
+                line_number: 2,
+                kind: io::ErrorKind::InvalidData
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_validate() {
+        let input = include_str!("../data/input_test.txt");
+        assert!(validate(input, &HALVES, &LETTERS).is_empty());
+
+        assert_eq!(
+            validate("abab\nabc\nab-b\n\n", &HALVES, &LETTERS),
+            vec![
+                RucksackError::UnevenLength {
+                    line_number: 2,
+                    len: 3,
+                    compartments: 2
+                },
+                RucksackError::InvalidItem {
+                    line_number: 3,
+                    item: '-'
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_custom_alphabet() -> Result<(), Box<dyn Error>> {
+        let digits = Alphabet::new("0123456789")?;
+        assert_eq!(sum_priorities("1223\n5985\n", &HALVES, &digits)?, 3 + 6);
+        assert!(sum_priorities("1223", &HALVES, &LETTERS).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_sum_priorities() {
+        let input = include_str!("../data/input_test.txt");
+        assert_eq!(sum_priorities(input, &HALVES, &LETTERS), Ok(157));
+
+        assert_eq!(
+            sum_priorities("vJrwpWtwJgWrhcsFMMfFFhFp\nabcdef\n", &HALVES, &LETTERS),
+            Err(RucksackError::NoCommonItem { line_number: 2 })
+        );
+        assert_eq!(
+            sum_priorities("abab", &HALVES, &LETTERS),
+            Err(RucksackError::AmbiguousCommonItem {
+                line_number: 1,
+                items: vec!['a', 'b']
+            })
+        );
+        assert_eq!(
+            sum_priorities("abab\nabcab\n", &HALVES, &LETTERS),
+            Err(RucksackError::UnevenLength {
+                line_number: 2,
+                len: 5,
+                compartments: 2
+            })
+        );
+    }
+
+    #[test]
+    fn test_report() -> Result<(), RucksackError> {
+        let input = include_str!("../data/input_test.txt");
+        assert!(report(&parse_rucksacks(input, &HALVES, &LETTERS)?, 3, &LETTERS)?.is_empty());
+
+        let rucksacks = parse_rucksacks("abab\nabcd\nxyxy\nxyzw\n", &HALVES, &LETTERS)?;
+        let report = report(&rucksacks, 2, &LETTERS)?;
+        let lines: Vec<String> = report.iter().map(|v| v.to_string()).collect();
+        assert_eq!(
+            lines,
+            vec![
+                "line 1: rucksack shares ab",
+                "line 2: rucksack shares nothing",
+                "line 3: rucksack shares xy",
+                "line 4: rucksack shares nothing",
+                "line 1: group shares ab",
+                "line 3: group shares xy",
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_find_badge() {
+        assert_eq!(
+            find_badge(
+                &[
+                    rucksack("vJrwpWtwJgWrhcsFMMfFFhFp"),
+                    rucksack("jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL"),
+                    rucksack("PmmdzqPrVvPwwTWBwg")
+                ],
+                &LETTERS
+            ),
+            Common::Exactly('r')
+        );
+        assert_eq!(
+            find_badge(
+                &[
+                    rucksack("wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn"),
+                    rucksack("ttgJtRGJQctTZtZT"),
+                    rucksack("CrZsJsPPZsGzwwsLwLmpwMDw")
+                ],
+                &LETTERS
+            ),
+            Common::Exactly('Z')
+        );
+        assert_eq!(
+            find_badge(
+                &[rucksack("vJrwpWtwJgWr"), rucksack("hcsFMMfFFhFp")],
+                &LETTERS
+            ),
+            Common::Exactly('p')
+        );
+    }
+
+    #[test]
+    fn test_sum_badge_priorities() {
+        let input = include_str!("../data/input_test.txt");
+        assert_eq!(sum_badge_priorities(input, 3, &HALVES, &LETTERS), Ok(70));
+    }
+
+    #[test]
+    fn test_sum_badge_priorities_group_sizes() {
+        let input = include_str!("../data/input_test.txt");
+
+        // Every rucksack on its own is a group, badged by all of its items.
+        assert!(matches!(
+            sum_badge_priorities(input, 1, &HALVES, &LETTERS),
+            Err(RucksackError::AmbiguousCommonItem { line_number: 1, .. })
+        ));
+        assert_eq!(
+            sum_badge_priorities(input, 4, &HALVES, &LETTERS),
+            Err(RucksackError::IncompleteGroup {
+                line_number: 5,
+                members: 2,
+                group_size: 4,
+            })
+        );
+        assert_eq!(
+            sum_badge_priorities(input, 0, &HALVES, &LETTERS),
+            Err(RucksackError::InvalidGroupSize)
+        );
+    }
+}
@@ -0,0 +1,208 @@
+use std::io::{self, Write};
+
+use crate::alphabet::Alphabet;
+use crate::rucksack::Rucksack;
+
+/// Per item type counts over a list of rucksacks, indexed by priority.
+pub struct Frequencies {
+    /// Rucksacks carrying the item at all.
+    pub contained: [usize; 64],
+    /// Rucksacks whose compartments share the item.
+    pub both: [usize; 64],
+    /// Rucksacks carrying both items of a pair.
+    pub pairs: [[usize; 64]; 64],
+}
+
+impl Frequencies {
+    pub fn from_rucksacks(rucksacks: &[Rucksack]) -> Self {
+        let mut freq = Frequencies {
+            contained: [0; 64],
+            both: [0; 64],
+            pairs: [[0; 64]; 64],
+        };
+
+        for rucksack in rucksacks {
+            let items = rucksack.items();
+            for p in items {
+                freq.contained[p as usize] += 1;
+                for q in items {
+                    freq.pairs[p as usize][q as usize] += 1;
+                }
+            }
+            for p in rucksack.shared() {
+                freq.both[p as usize] += 1;
+            }
+        }
+
+        freq
+    }
+}
+
+/// The alphabet's items in priority order.
+fn items(alphabet: &Alphabet) -> Vec<(usize, char)> {
+    (1..64)
+        .map_while(|p| alphabet.item(p).map(|c| (p as usize, c)))
+        .collect()
+}
+
+pub fn write_table<W: Write>(
+    freq: &Frequencies,
+    alphabet: &Alphabet,
+    mut out: W,
+) -> io::Result<()> {
+    let items = items(alphabet);
+
+    writeln!(out, "item priority rucksacks both")?;
+    for &(p, c) in &items {
+        writeln!(
+            out,
+            "{:>4} {:>8} {:>9} {:>4}",
+            c, p, freq.contained[p], freq.both[p]
+        )?;
+    }
+
+    writeln!(out)?;
+    write!(out, "   ")?;
+    for &(_, c) in &items {
+        write!(out, " {:>3}", c)?;
+    }
+    writeln!(out)?;
+    for &(p, c) in &items {
+        write!(out, "{:>3}", c)?;
+        for &(q, _) in &items {
+            write!(out, " {:>3}", freq.pairs[p][q])?;
+        }
+        writeln!(out)?;
+    }
+
+    Ok(())
+}
+
+/// An item as a CSV field, quoted as RFC 4180 asks when it is a `,` or `"`.
+fn csv_field(item: char) -> String {
+    match item {
+        ',' => "\",\"".to_string(),
+        '"' => "\"\"\"\"".to_string(),
+        _ => item.to_string(),
+    }
+}
+
+/// Writes one row per item with its counts followed by its co-occurrence
+/// with every other item.
+pub fn write_csv<W: Write>(freq: &Frequencies, alphabet: &Alphabet, mut out: W) -> io::Result<()> {
+    let items = items(alphabet);
+
+    write!(out, "item,priority,rucksacks,both")?;
+    for &(_, c) in &items {
+        write!(out, ",{}", csv_field(c))?;
+    }
+    writeln!(out)?;
+
+    for &(p, c) in &items {
+        write!(
+            out,
+            "{},{},{},{}",
+            csv_field(c),
+            p,
+            freq.contained[p],
+            freq.both[p]
+        )?;
+        for &(q, _) in &items {
+            write!(out, ",{}", freq.pairs[p][q])?;
+        }
+        writeln!(out)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layout::Layout;
+    use crate::rucksack::parse_rucksacks;
+    use crate::LETTERS;
+
+    fn frequencies(input: &str) -> Frequencies {
+        Frequencies::from_rucksacks(&parse_rucksacks(input, &Layout::halves(), &LETTERS).unwrap())
+    }
+
+    #[test]
+    fn test_frequencies() {
+        let freq = frequencies(include_str!("../data/input_test.txt"));
+        let p = |c| LETTERS.priority(c).unwrap() as usize;
+
+        // Every rucksack carries exactly one item in both compartments.
+        assert_eq!(freq.both.iter().sum::<usize>(), 6);
+        assert_eq!(freq.both[p('p')], 1);
+        assert_eq!(freq.both[p('L')], 1);
+
+        // The badges are carried by every member of their group.
+        assert!(freq.contained[p('r')] >= 3);
+        assert!(freq.contained[p('Z')] >= 3);
+        assert_eq!(freq.contained[p('x')], 0);
+
+        assert_eq!(freq.pairs[p('r')][p('r')], freq.contained[p('r')]);
+        assert_eq!(freq.pairs[p('a')][p('b')], freq.pairs[p('b')][p('a')]);
+    }
+
+    #[test]
+    fn test_write_csv() -> io::Result<()> {
+        let alphabet = Alphabet::new("abc").unwrap();
+        let freq = Frequencies::from_rucksacks(
+            &parse_rucksacks("abab\nacbc\n", &Layout::halves(), &alphabet).unwrap(),
+        );
+
+        let mut out = Vec::new();
+        write_csv(&freq, &alphabet, &mut out)?;
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "item,priority,rucksacks,both,a,b,c\n\
+             a,1,2,1,2,2,1\n\
+             b,2,2,1,2,2,1\n\
+             c,3,1,1,1,1,1\n"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_csv_quotes_items() -> io::Result<()> {
+        let alphabet = Alphabet::new("a,\"").unwrap();
+        let freq = Frequencies::from_rucksacks(
+            &parse_rucksacks("a,a\"\n", &Layout::halves(), &alphabet).unwrap(),
+        );
+
+        let mut out = Vec::new();
+        write_csv(&freq, &alphabet, &mut out)?;
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "item,priority,rucksacks,both,a,\",\",\"\"\"\"\n\
+             a,1,1,1,1,1,1\n\
+             \",\",2,1,0,1,1,1\n\
+             \"\"\"\",3,1,0,1,1,1\n"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_table() -> io::Result<()> {
+        let alphabet = Alphabet::new("ab").unwrap();
+        let freq = Frequencies::from_rucksacks(
+            &parse_rucksacks("abab\n", &Layout::halves(), &alphabet).unwrap(),
+        );
+
+        let mut out = Vec::new();
+        write_table(&freq, &alphabet, &mut out)?;
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "item priority rucksacks both\n   \
+             a        1         1    1\n   \
+             b        2         1    1\n\
+             \n      \
+             a   b\n  \
+             a   1   1\n  \
+             b   1   1\n"
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,225 @@
+use crate::section_range::SectionRange;
+
+/// A run of sections cleaned by the same number of elves.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct Segment {
+    pub range: SectionRange,
+    pub elves: usize,
+}
+
+/// How many elves clean each section between the lowest and the highest
+/// assigned one, as runs of equal counts.
+#[derive(Debug, Default)]
+pub struct Coverage {
+    segments: Vec<Segment>,
+}
+
+impl Coverage {
+    /// Sweeps over the range boundaries in order, keeping count of the
+    /// ranges that are open, so the cost depends on the number of ranges
+    /// rather than their lengths.
+    pub fn new<I>(ranges: I) -> Self
+    where
+        I: IntoIterator<Item = SectionRange>,
+    {
+        // A range closes just past its end, which can be past u32::MAX.
+        let mut events: Vec<(u64, bool)> = Vec::new();
+        for range in ranges {
+            events.push((u64::from(range.start()), true));
+            events.push((u64::from(range.end()) + 1, false));
+        }
+        events.sort_unstable();
+
+        let mut segments: Vec<Segment> = Vec::new();
+        let mut elves = 0;
+        let mut i = 0;
+        while i < events.len() {
+            let position = events[i].0;
+            while i < events.len() && events[i].0 == position {
+                match events[i].1 {
+                    true => elves += 1,
+                    false => elves -= 1,
+                }
+                i += 1;
+            }
+
+            // Past the last event nobody is left.
+            let Some(&(next, _)) = events.get(i) else {
+                break;
+            };
+            let range =
+                SectionRange::new(position as u32, (next - 1) as u32).expect("events are sorted");
+
+            match segments.last_mut() {
+                Some(last) if last.elves == elves => {
+                    last.range = SectionRange::new(last.range.start(), range.end())
+                        .expect("segments are in order");
+                }
+                _ => segments.push(Segment { range, elves }),
+            }
+        }
+
+        Coverage { segments }
+    }
+
+    pub fn segments(&self) -> &[Segment] {
+        &self.segments
+    }
+
+    /// From the lowest to the highest assigned section.
+    pub fn span(&self) -> Option<SectionRange> {
+        let first = self.segments.first()?;
+        let last = self.segments.last()?;
+        SectionRange::new(first.range.start(), last.range.end())
+    }
+
+    /// Merges the neighbouring segments whose count matches.
+    fn runs<F>(&self, matches: F) -> Vec<SectionRange>
+    where
+        F: Fn(usize) -> bool,
+    {
+        let mut runs: Vec<SectionRange> = Vec::new();
+        let mut extends = false;
+        for segment in &self.segments {
+            if !matches(segment.elves) {
+                extends = false;
+                continue;
+            }
+
+            match runs.last_mut() {
+                Some(last) if extends => {
+                    *last = SectionRange::new(last.start(), segment.range.end())
+                        .expect("segments are in order");
+                }
+                _ => runs.push(segment.range),
+            }
+            extends = true;
+        }
+        runs
+    }
+
+    /// The sections at least one elf cleans.
+    pub fn union(&self) -> Vec<SectionRange> {
+        self.runs(|elves| elves > 0)
+    }
+
+    /// The sections within the span that nobody cleans.
+    pub fn gaps(&self) -> Vec<SectionRange> {
+        self.runs(|elves| elves == 0)
+    }
+
+    /// The sections more than `limit` elves clean.
+    pub fn crowded(&self, limit: usize) -> Vec<SectionRange> {
+        self.runs(|elves| elves > limit)
+    }
+
+    /// How many elves clean the section, found by binary search over the
+    /// segments.
+    pub fn elves_at(&self, section: u32) -> usize {
+        let i = self.segments.partition_point(|s| s.range.end() < section);
+        match self.segments.get(i) {
+            Some(segment) if segment.range.start() <= section => segment.elves,
+            _ => 0,
+        }
+    }
+
+    /// One character per section of the span: `.` when nobody cleans it,
+    /// the number of elves up to 9, and `+` past that.
+    pub fn bar(&self) -> String {
+        let mut bar = String::new();
+        for segment in &self.segments {
+            let c = match segment.elves {
+                0 => '.',
+                n @ 1..=9 => char::from(b'0' + n as u8),
+                _ => '+',
+            };
+            bar.extend(std::iter::repeat_n(c, segment.range.length() as usize));
+        }
+        bar
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::section_range::range;
+
+    fn coverage(ranges: &[(u32, u32)]) -> Coverage {
+        Coverage::new(ranges.iter().map(|&(s, e)| range(s, e)))
+    }
+
+    #[test]
+    fn test_segments() {
+        let coverage = coverage(&[(2, 4), (6, 8), (3, 5), (9, 9)]);
+        assert_eq!(
+            coverage.segments(),
+            [
+                Segment {
+                    range: range(2, 2),
+                    elves: 1
+                },
+                Segment {
+                    range: range(3, 4),
+                    elves: 2
+                },
+                Segment {
+                    range: range(5, 9),
+                    elves: 1
+                },
+            ]
+        );
+        assert_eq!(coverage.span(), Some(range(2, 9)));
+        assert_eq!(coverage.bar(), "12211111");
+    }
+
+    #[test]
+    fn test_union_gaps_and_crowded() {
+        let coverage = coverage(&[(2, 4), (6, 8), (2, 3), (7, 9), (12, 12), (7, 7)]);
+        assert_eq!(
+            coverage.union(),
+            vec![range(2, 4), range(6, 9), range(12, 12)]
+        );
+        assert_eq!(coverage.gaps(), vec![range(5, 5), range(10, 11)]);
+        assert_eq!(coverage.crowded(1), vec![range(2, 3), range(7, 8)]);
+        assert_eq!(coverage.crowded(2), vec![range(7, 7)]);
+        assert_eq!(coverage.bar(), "221.1321..1");
+    }
+
+    #[test]
+    fn test_matches_counting() {
+        let ranges = [(1, 5), (3, 3), (4, 9), (9, 12), (15, 20), (2, 18)];
+        let coverage = coverage(&ranges);
+
+        let counted: String = (1..=20)
+            .map(|section| {
+                let elves = ranges
+                    .iter()
+                    .filter(|&&(s, e)| s <= section && section <= e)
+                    .count();
+                match elves {
+                    0 => '.',
+                    n => char::from(b'0' + n as u8),
+                }
+            })
+            .collect();
+        assert_eq!(coverage.bar(), counted);
+        for (section, c) in (1..=20).zip(counted.chars()) {
+            assert_eq!(
+                coverage.elves_at(section),
+                c.to_digit(10).unwrap_or(0) as usize
+            );
+        }
+        assert_eq!(coverage.elves_at(0), 0);
+        assert_eq!(coverage.elves_at(21), 0);
+    }
+
+    #[test]
+    fn test_extremes() {
+        assert_eq!(Coverage::new([]).span(), None);
+        assert!(Coverage::new([]).union().is_empty());
+
+        let coverage = coverage(&[(0, u32::MAX), (u32::MAX, u32::MAX)]);
+        assert_eq!(coverage.union(), vec![range(0, u32::MAX)]);
+        assert_eq!(coverage.crowded(1), vec![range(u32::MAX, u32::MAX)]);
+    }
+}
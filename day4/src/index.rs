@@ -0,0 +1,202 @@
+use crate::coverage::Coverage;
+use crate::section_range::SectionRange;
+
+/// One elf's range, with where it came from.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct Assignment {
+    pub line_number: usize,
+    /// The elf's position on its line, from 0.
+    pub member: usize,
+    pub range: SectionRange,
+}
+
+/// Answers which assignments cover a section or meet a range, and how many
+/// elves clean a section, without scanning every assignment.
+///
+/// The assignments are sorted by start and treated as a balanced binary
+/// tree, with each node's subtree split at its midpoint and annotated with
+/// the highest end below it, so a search can skip every subtree that ends
+/// before the query or starts after it.
+#[derive(Debug)]
+pub struct Index {
+    assignments: Vec<Assignment>,
+    max_end: Vec<u32>,
+    coverage: Coverage,
+}
+
+impl Index {
+    pub fn new(mut assignments: Vec<Assignment>) -> Self {
+        assignments.sort_by_key(|a| (a.range.start(), a.line_number, a.member));
+        let coverage = Coverage::new(assignments.iter().map(|a| a.range));
+
+        let mut index = Index {
+            max_end: vec![0; assignments.len()],
+            assignments,
+            coverage,
+        };
+        index.annotate(0, index.assignments.len());
+        index
+    }
+
+    pub fn len(&self) -> usize {
+        self.assignments.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.assignments.is_empty()
+    }
+
+    fn annotate(&mut self, lo: usize, hi: usize) -> Option<u32> {
+        if lo >= hi {
+            return None;
+        }
+
+        let mid = lo + (hi - lo) / 2;
+        let mut max_end = self.assignments[mid].range.end();
+        for end in [self.annotate(lo, mid), self.annotate(mid + 1, hi)] {
+            max_end = max_end.max(end.unwrap_or_default());
+        }
+        self.max_end[mid] = max_end;
+        Some(max_end)
+    }
+
+    fn search<'a>(
+        &'a self,
+        query: &SectionRange,
+        lo: usize,
+        hi: usize,
+        found: &mut Vec<&'a Assignment>,
+    ) {
+        if lo >= hi {
+            return;
+        }
+
+        let mid = lo + (hi - lo) / 2;
+        if self.max_end[mid] < query.start() {
+            return;
+        }
+
+        self.search(query, lo, mid, found);
+        let assignment = &self.assignments[mid];
+        if assignment.range.overlaps(query) {
+            found.push(assignment);
+        }
+        // Everything to the right starts no earlier than this one.
+        if assignment.range.start() <= query.end() {
+            self.search(query, mid + 1, hi, found);
+        }
+    }
+
+    /// The assignments sharing a section with the range, in line order.
+    pub fn intersecting(&self, range: &SectionRange) -> Vec<&Assignment> {
+        let mut found = Vec::new();
+        self.search(range, 0, self.assignments.len(), &mut found);
+        found.sort_by_key(|a| (a.line_number, a.member));
+        found
+    }
+
+    /// The assignments that include the section, in line order.
+    pub fn covering(&self, section: u32) -> Vec<&Assignment> {
+        self.intersecting(&SectionRange::new(section, section).expect("a single section"))
+    }
+
+    /// How many elves clean the section.
+    pub fn count(&self, section: u32) -> usize {
+        self.coverage.elves_at(section)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::section_range::range;
+
+    fn index(lines: &[&[(u32, u32)]]) -> Index {
+        let mut assignments = Vec::new();
+        for (i, line) in lines.iter().enumerate() {
+            for (member, &(start, end)) in line.iter().enumerate() {
+                assignments.push(Assignment {
+                    line_number: i + 1,
+                    member,
+                    range: range(start, end),
+                });
+            }
+        }
+        Index::new(assignments)
+    }
+
+    fn lines(found: &[&Assignment]) -> Vec<(usize, usize)> {
+        found.iter().map(|a| (a.line_number, a.member)).collect()
+    }
+
+    #[test]
+    fn test_queries() {
+        let index = index(&[
+            &[(2, 4), (6, 8)],
+            &[(2, 3), (4, 5)],
+            &[(5, 7), (7, 9)],
+            &[(2, 8), (3, 7)],
+            &[(6, 6), (4, 6)],
+            &[(2, 6), (4, 8)],
+        ]);
+        assert_eq!(index.len(), 12);
+
+        assert_eq!(
+            lines(&index.covering(7)),
+            vec![(1, 1), (3, 0), (3, 1), (4, 0), (4, 1), (6, 1)]
+        );
+        assert_eq!(index.count(7), 6);
+        assert_eq!(lines(&index.covering(1)), vec![]);
+        assert_eq!(index.count(1), 0);
+        assert_eq!(index.count(100), 0);
+
+        assert_eq!(
+            lines(&index.intersecting(&range(8, 20))),
+            vec![(1, 1), (3, 1), (4, 0), (6, 1)]
+        );
+    }
+
+    #[test]
+    fn test_matches_scan() {
+        // Ranges from a small generator, checked against a plain scan.
+        let mut state = 2022u32;
+        let mut next = |limit: u32| {
+            state = state.wrapping_mul(1_103_515_245).wrapping_add(12345);
+            (state >> 16) % limit
+        };
+        let mut ranges = Vec::new();
+        for _ in 0..200 {
+            let start = next(90);
+            ranges.push((start, start + next(15)));
+        }
+        let index = index(&[&ranges]);
+
+        for start in 0..110 {
+            let scanned = ranges
+                .iter()
+                .filter(|&&(s, e)| s <= start && start <= e)
+                .count();
+            assert_eq!(index.covering(start).len(), scanned);
+            assert_eq!(index.count(start), scanned);
+
+            let query = range(start, start + 7);
+            let scanned: Vec<usize> = (0..ranges.len())
+                .filter(|&m| range(ranges[m].0, ranges[m].1).overlaps(&query))
+                .collect();
+            let found: Vec<usize> = index
+                .intersecting(&query)
+                .iter()
+                .map(|a| a.member)
+                .collect();
+            assert_eq!(found, scanned);
+        }
+    }
+
+    #[test]
+    fn test_empty() {
+        let index = Index::new(Vec::new());
+        assert!(index.is_empty());
+        assert!(index.covering(5).is_empty());
+        assert_eq!(index.count(5), 0);
+    }
+}
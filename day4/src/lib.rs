@@ -0,0 +1,22 @@
+pub mod coverage;
+pub mod index;
+pub mod optimize;
+pub mod parse;
+pub mod region;
+pub mod relation;
+pub mod render;
+pub mod section_box;
+pub mod section_range;
+pub mod stream;
+pub mod team;
+
+pub use coverage::Coverage;
+pub use index::{Assignment, Index};
+pub use parse::{ParseError, RangeError, Reversed};
+pub use region::Region;
+pub use relation::Relation;
+pub use render::Canvas;
+pub use section_box::SectionBox;
+pub use section_range::SectionRange;
+pub use stream::{evaluate, Tally};
+pub use team::Team;
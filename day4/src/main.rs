@@ -1,4 +1,4 @@
-/* 
+/*
 --- Day 4: Camp Cleanup ---
 Space needs to be cleared before the last supplies can be unloaded from the ships, and so several Elves have been assigned the job of cleaning up sections of the camp. Every section has a unique ID number, and each Elf is assigned a range of section IDs.
 
@@ -64,69 +64,475 @@ At this point, you should return to your Advent calendar and try another puzzle.
 If you still want to see it, you can get your puzzle input.
 
 */
-use std::collections::HashSet;
+use std::env;
 use std::error::Error;
+use std::io::{self, BufRead};
+use std::num::NonZeroUsize;
+
+use day4::optimize::rebalance;
+use day4::parse::{parse_pairs, parse_teams, validate, validate_pairs, ParseError};
+use day4::region::shared_size;
+use day4::{
+    evaluate, Assignment, Canvas, Coverage, Index, Relation, Reversed, SectionBox, SectionRange,
+    Tally, Team,
+};
+
+const INPUT: &str = include_str!("../data/input.txt");
+
+type Pair = (SectionRange, SectionRange);
+
+fn print_teams(teams: &[Team]) {
+    for (i, team) in teams.iter().enumerate() {
+        let members = team.members();
+        println!("line {}: {} elves", i + 1, members.len());
+
+        let redundant: Vec<String> = team
+            .redundant()
+            .iter()
+            .map(|&m| format!("{} ({})", m + 1, members[m]))
+            .collect();
+        match redundant.is_empty() {
+            true => println!("  redundant: none"),
+            false => println!("  redundant: {}", redundant.join(", ")),
+        }
+
+        match team.common() {
+            Some(common) => println!("  covered by everyone: {}", common),
+            None => println!("  covered by everyone: none"),
+        }
+
+        print!("  overlap");
+        for m in 1..=members.len() {
+            print!(" {:>5}", m);
+        }
+        println!();
+        for (m, row) in team.overlap_matrix().iter().enumerate() {
+            print!("  {:>7}", m + 1);
+            for sections in row {
+                print!(" {:>5}", sections);
+            }
+            println!();
+        }
+    }
+}
 
-fn expand_range(range_str: &str) -> Result<HashSet<u32>, Box<dyn Error>> {
-    let parts = range_str.split_once('-').unwrap();
+/// The most sections `--bar` draws, one character each.
+const MAX_BAR: u64 = 10_000;
+
+/// Lists the ranges as `2-4,6-8`, or `none`.
+fn range_list(ranges: &[SectionRange]) -> String {
+    match ranges.is_empty() {
+        true => "none".to_owned(),
+        false => {
+            let ranges: Vec<String> = ranges.iter().map(|r| r.to_string()).collect();
+            ranges.join(",")
+        }
+    }
+}
+
+fn print_coverage(teams: &[Team], crowded: usize, bar: bool) {
+    let coverage = Coverage::new(teams.iter().flat_map(|t| t.members().iter().copied()));
+
+    println!("covered {}", range_list(&coverage.union()));
+    println!("gaps {}", range_list(&coverage.gaps()));
+    println!(
+        "more than {} elves {}",
+        crowded,
+        range_list(&coverage.crowded(crowded))
+    );
+    if bar {
+        match coverage.span() {
+            Some(span) if span.length() > MAX_BAR => {
+                println!("{} sections are too many to draw", span.length());
+            }
+            Some(span) => {
+                println!("from {}", span.start());
+                println!("{}", coverage.bar());
+            }
+            None => {}
+        }
+    }
+}
 
-    let begin: u32 = parts.0.parse()?;
-    let end: u32 = parts.1.parse()?;
+/// The sections to draw, from section 1 as the puzzle does when one section
+/// a column fits, and otherwise only those the ranges cover.
+fn render_span(start: u32, end: u32, columns: usize) -> Option<SectionRange> {
+    match usize::try_from(end).is_ok_and(|end| end <= columns) {
+        true => SectionRange::new(start.min(1), end),
+        false => SectionRange::new(start, end),
+    }
+}
+
+/// Draws every team on the selected lines against one shared scale, with
+/// the sections everyone cleans highlighted below.
+fn print_render(
+    teams: &[Team],
+    lines: Option<SectionRange>,
+    width: usize,
+) -> Result<(), Box<dyn Error>> {
+    let teams: Vec<(u32, &Team)> = (1..)
+        .zip(teams)
+        .filter(|&(line_number, _)| {
+            lines.is_none_or(|l| l.start() <= line_number && line_number <= l.end())
+        })
+        .collect();
+
+    let ranges = teams.iter().flat_map(|(_, team)| team.members());
+    let (Some(start), Some(end)) = (
+        ranges.clone().map(SectionRange::start).min(),
+        ranges.map(SectionRange::end).max(),
+    ) else {
+        return Ok(());
+    };
+
+    let label = teams
+        .iter()
+        .flat_map(|(_, team)| team.members())
+        .map(|r| r.to_string().len())
+        .max()
+        .unwrap_or_default()
+        + "overlap ".len();
+    let columns = width.saturating_sub(label + 2).max(10);
+    let span = render_span(start, end, columns).ok_or("no sections to draw")?;
+    let canvas = Canvas::new(span, columns);
+    if canvas.is_scaled() {
+        println!("sections {} over {} columns", span, canvas.columns());
+        println!();
+    }
+
+    for (line_number, team) in &teams {
+        match team.redundant().is_empty() {
+            true => println!("line {}", line_number),
+            false => println!("line {}: fully contains", line_number),
+        }
+        for member in team.members() {
+            println!("{}  {}", canvas.draw(member), member);
+        }
+        if let Some(common) = team.common() {
+            println!("{}  overlap {}", canvas.highlight(&common), common);
+        }
+        println!();
+    }
 
-    Ok((begin..=end).collect())
+    Ok(())
 }
 
-fn parse_line(line: &str) -> Result<(HashSet<u32>, HashSet<u32>), Box<dyn Error>> {
-    let splits = line.split_once(',').unwrap();
-    Ok((expand_range(splits.0)?, expand_range(splits.1)?))
+/// Writes the rebalanced assignments to stdout in the input's format, and
+/// how much duplicated work that saves to stderr.
+fn print_optimized(pairs: &[Pair]) {
+    let rebalanced: Vec<_> = pairs.iter().map(|(a, b)| rebalance(a, b)).collect();
+
+    for (a, b) in &rebalanced {
+        println!("{},{}", a, b);
+    }
+    eprintln!(
+        "overlapping sections {} before, {} after",
+        shared_size(pairs),
+        shared_size(&rebalanced)
+    );
 }
 
-fn find_intersection(s1: &HashSet<u32>, s2: &HashSet<u32>) -> HashSet<u32> {
-    s1.intersection(s2).copied().collect()
+enum Query {
+    Cover(u32),
+    Intersect(SectionRange),
+    Count(u32),
 }
 
-fn fully_contains(s1: &HashSet<u32>, s2: &HashSet<u32>) -> bool {
-    let isect = find_intersection(s1, s2);
-    isect == *s1 || isect == *s2
+impl Query {
+    fn parse(kind: &str, value: &str) -> Result<Self, Box<dyn Error>> {
+        match kind {
+            "cover" => Ok(Query::Cover(value.parse()?)),
+            "intersect" => Ok(Query::Intersect(value.parse()?)),
+            "count" => Ok(Query::Count(value.parse()?)),
+            _ => Err(format!("unknown query {}", kind).into()),
+        }
+    }
 }
 
-fn part1() -> usize {
-    let input = include_str!("../data/input.txt");
-    input
-        .lines()
-        .map(|l| parse_line(l).unwrap())
-        .filter(|(l, r)| fully_contains(l, r))
-        .count()
+fn build_index(teams: &[Team]) -> Index {
+    let mut assignments = Vec::new();
+    for (i, team) in teams.iter().enumerate() {
+        for (member, &range) in team.members().iter().enumerate() {
+            assignments.push(Assignment {
+                line_number: i + 1,
+                member,
+                range,
+            });
+        }
+    }
+
+    Index::new(assignments)
 }
 
-fn part2() -> usize {
-    let input = include_str!("../data/input.txt");
-    input
-        .lines()
-        .map(|l| parse_line(l).unwrap())
-        .filter(|(l, r)| !find_intersection(l, r).is_empty())
-        .count()
+fn print_query(teams: &[Team], query: &Query) {
+    let index = build_index(teams);
+    let found = match query {
+        Query::Cover(section) => index.covering(*section),
+        Query::Intersect(range) => index.intersecting(range),
+        Query::Count(section) => {
+            println!("section {}: {} elves", section, index.count(*section));
+            return;
+        }
+    };
+
+    for assignment in &found {
+        println!(
+            "line {} elf {}: {}",
+            assignment.line_number,
+            assignment.member + 1,
+            assignment.range
+        );
+    }
+    println!("{} assignments", found.len());
 }
 
-fn main() {
-    println!("Part1 answer {}", part1());
-    println!("Part2 answer {}", part2());
+/// Counts the pairs in each relation, or lists the lines in one of them.
+fn print_relations(pairs: &[Pair], filter: Option<Relation>) {
+    let mut counts = [0usize; Relation::ALL.len()];
+    for (i, (a, b)) in pairs.iter().enumerate() {
+        let relation = Relation::between(a, b);
+        counts[relation as usize] += 1;
+
+        if filter == Some(relation) {
+            println!("line {}: {},{}", i + 1, a, b);
+        }
+    }
+
+    if filter.is_none() {
+        for relation in Relation::ALL {
+            println!("{:>13} {}", relation, counts[relation as usize]);
+        }
+    }
+}
+
+/// Prints both parts, and the area shared within each pair for boxes.
+fn print_answers(tally: &Tally, boxes: bool) {
+    println!("Part1 answer {}", tally.contained);
+    println!("Part2 answer {}", tally.overlapping);
+    if boxes {
+        println!("Overlapping area {}", tally.shared);
+    }
+}
+
+/// Answers both parts in one pass over the pairs as they are read, writing
+/// the running totals to stderr.
+fn print_stream<B: BufRead>(
+    reader: B,
+    boxes: bool,
+    reversed: Reversed,
+    every: Option<NonZeroUsize>,
+) -> Result<(), ParseError> {
+    let progress = |tally: &Tally| eprintln!("{}", tally);
+    let tally = match boxes {
+        true => evaluate::<SectionBox, _, _>(reader, reversed, every, progress),
+        false => evaluate::<SectionRange, _, _>(reader, reversed, every, progress),
+    }?;
+
+    print_answers(&tally, boxes);
+    Ok(())
+}
+
+/// The terminal width from `COLUMNS`, falling back to 80.
+fn terminal_width() -> usize {
+    env::var("COLUMNS")
+        .ok()
+        .and_then(|c| c.parse().ok())
+        .unwrap_or(80)
+}
+
+enum Mode {
+    Answers,
+    Teams,
+    Coverage,
+    Render,
+    Optimize,
+    Query(Query),
+    Relations(Option<Relation>),
+    /// Answers both parts for pairs read from stdin.
+    Stream,
+}
+
+struct Options {
+    mode: Mode,
+    crowded: usize,
+    bar: bool,
+    lines: Option<SectionRange>,
+    width: Option<usize>,
+    reversed: Reversed,
+    every: Option<NonZeroUsize>,
+    /// Reads the pairs as boxes rather than ranges.
+    boxes: bool,
+}
+
+fn parse_args<I>(mut args: I) -> Result<Options, Box<dyn Error>>
+where
+    I: Iterator<Item = String>,
+{
+    let mut options = Options {
+        mode: Mode::Answers,
+        crowded: 1,
+        bar: false,
+        lines: None,
+        width: None,
+        reversed: Reversed::Reject,
+        every: None,
+        boxes: false,
+    };
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--teams" => options.mode = Mode::Teams,
+            "--coverage" => options.mode = Mode::Coverage,
+            "--crowded" => {
+                options.crowded = args.next().ok_or("--crowded needs a value")?.parse()?;
+            }
+            "--bar" => options.bar = true,
+            "--render" => options.mode = Mode::Render,
+            "--optimize" => options.mode = Mode::Optimize,
+            "--boxes" => options.boxes = true,
+            "--stream" => options.mode = Mode::Stream,
+            "--every" => {
+                options.every = Some(args.next().ok_or("--every needs a value")?.parse()?);
+            }
+            "--normalize" => options.reversed = Reversed::Swap,
+            "--relations" => options.mode = Mode::Relations(None),
+            "--relation" => {
+                let relation = args.next().ok_or("--relation needs a value")?.parse()?;
+                options.mode = Mode::Relations(Some(relation));
+            }
+            "--query" => {
+                let kind = args.next().ok_or("--query needs a kind")?;
+                let value = args.next().ok_or("--query needs a value")?;
+                options.mode = Mode::Query(Query::parse(&kind, &value)?);
+            }
+            "--lines" => {
+                options.lines = Some(args.next().ok_or("--lines needs a value")?.parse()?);
+            }
+            "--width" => {
+                options.width = Some(args.next().ok_or("--width needs a value")?.parse()?);
+            }
+            _ => return Err(format!("unknown argument {}", arg).into()),
+        }
+    }
+
+    if options.boxes && !matches!(options.mode, Mode::Answers | Mode::Stream) {
+        return Err("--boxes only works with the answers or --stream".into());
+    }
+
+    Ok(options)
+}
+
+/// The embedded input, once every line `check` rejects has been reported.
+fn load(
+    check: fn(&str, Reversed) -> Vec<ParseError>,
+    reversed: Reversed,
+) -> Result<&'static str, Box<dyn Error>> {
+    let errors = check(INPUT, reversed);
+    if !errors.is_empty() {
+        for error in &errors {
+            eprintln!("{}", error);
+        }
+        return Err(format!("{} invalid lines", errors.len()).into());
+    }
+    Ok(INPUT)
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let options = parse_args(env::args().skip(1))?;
+    let reversed = options.reversed;
+
+    // The answers check each line as they read it, so only the modes that
+    // keep the whole input validate it up front.
+    match options.mode {
+        Mode::Answers => {
+            if let Err(error) = print_stream(INPUT.as_bytes(), options.boxes, reversed, None) {
+                // Only a bad input is read again, to report every wrong line.
+                match options.boxes {
+                    true => load(validate_pairs::<SectionBox>, reversed)?,
+                    false => load(validate_pairs::<SectionRange>, reversed)?,
+                };
+                return Err(error.to_string().into());
+            }
+        }
+        Mode::Stream => {
+            if let Err(error) =
+                print_stream(io::stdin().lock(), options.boxes, reversed, options.every)
+            {
+                eprintln!("{}", error);
+                return Err(format!("stopped at line {}", error.line_number).into());
+            }
+        }
+        Mode::Teams => print_teams(&parse_teams(load(validate, reversed)?, reversed)?),
+        Mode::Coverage => {
+            let teams = parse_teams(load(validate, reversed)?, reversed)?;
+            print_coverage(&teams, options.crowded, options.bar);
+        }
+        Mode::Render => {
+            let width = options.width.unwrap_or_else(terminal_width);
+            let teams = parse_teams(load(validate, reversed)?, reversed)?;
+            print_render(&teams, options.lines, width)?;
+        }
+        Mode::Optimize => {
+            let input = load(validate_pairs::<SectionRange>, reversed)?;
+            print_optimized(&parse_pairs(input, reversed)?);
+        }
+        Mode::Query(query) => {
+            print_query(&parse_teams(load(validate, reversed)?, reversed)?, &query);
+        }
+        Mode::Relations(filter) => {
+            let input = load(validate_pairs::<SectionRange>, reversed)?;
+            print_relations(&parse_pairs(input, reversed)?, filter);
+        }
+    }
+
+    Ok(())
 }
 
 #[cfg(test)]
 mod tests {
 
     use super::*;
+    use day4::region::fully_contains;
+    use day4::Region;
+    use std::collections::HashSet;
 
-    #[test]
-    fn test_parse_line() -> Result<(), Box<dyn Error>> {
-        let input = "2-4,6-8";
-        assert_eq!(
-            parse_line(input)?,
-            (HashSet::from([2, 3, 4]), HashSet::from([6, 7, 8]))
-        );
+    // The original set-based implementation, kept as a reference for the
+    // range arithmetic.
 
-        Ok(())
+    fn expand_range(range: &SectionRange) -> HashSet<u32> {
+        (range.start()..=range.end()).collect()
+    }
+
+    fn find_intersection(s1: &HashSet<u32>, s2: &HashSet<u32>) -> HashSet<u32> {
+        s1.intersection(s2).copied().collect()
+    }
+
+    fn sets_fully_contain(s1: &HashSet<u32>, s2: &HashSet<u32>) -> bool {
+        let isect = find_intersection(s1, s2);
+        isect == *s1 || isect == *s2
+    }
+
+    fn evaluate_str<R: Region>(input: &str) -> Tally {
+        evaluate::<R, _, _>(input.as_bytes(), Reversed::Reject, None, |_| {}).unwrap()
+    }
+
+    fn tally_pairs<R: Region>(pairs: &[(R, R)]) -> Tally {
+        let mut tally = Tally::default();
+        for (a, b) in pairs {
+            tally.add(a, b);
+        }
+        tally
+    }
+
+    /// Every range within `0..=max`.
+    fn small_ranges(max: u32) -> Vec<SectionRange> {
+        (0..=max)
+            .flat_map(|start| (start..=max).filter_map(move |end| SectionRange::new(start, end)))
+            .collect()
+    }
+
+    fn pairs(input: &str) -> Vec<Pair> {
+        parse_pairs(input, Reversed::Reject).unwrap()
     }
 
     #[test]
@@ -143,33 +549,226 @@ mod tests {
         let lhs = HashSet::from([2, 3, 4, 5]);
         let rhs = HashSet::from([3, 4]);
 
+        assert!(sets_fully_contain(&lhs, &rhs));
+        assert!(sets_fully_contain(&rhs, &lhs));
+
+        let rhs = HashSet::from([4, 5, 6]);
+        assert!(!sets_fully_contain(&lhs, &rhs));
+        assert!(!sets_fully_contain(&rhs, &lhs));
+
+        let lhs = SectionRange::new(2, 5).unwrap();
+        let rhs = SectionRange::new(3, 4).unwrap();
         assert!(fully_contains(&lhs, &rhs));
         assert!(fully_contains(&rhs, &lhs));
 
-        let rhs = HashSet::from([4, 5, 6]);
+        let rhs = SectionRange::new(4, 6).unwrap();
         assert!(!fully_contains(&lhs, &rhs));
         assert!(!fully_contains(&rhs, &lhs));
     }
 
+    #[test]
+    fn test_ranges_match_sets() {
+        let ranges = small_ranges(9);
+        for a in &ranges {
+            let sa = expand_range(a);
+            assert_eq!(a.length(), sa.len() as u64);
+
+            for b in &ranges {
+                let sb = expand_range(b);
+                let isect = find_intersection(&sa, &sb);
+
+                assert_eq!(a.contains(b), isect == sb, "{:?} {:?}", a, b);
+                assert_eq!(fully_contains(a, b), sets_fully_contain(&sa, &sb));
+                assert_eq!(a.overlaps(b), !isect.is_empty(), "{:?} {:?}", a, b);
+                assert_eq!(
+                    a.intersection(b)
+                        .map_or_else(HashSet::new, |r| expand_range(&r)),
+                    isect
+                );
+            }
+        }
+    }
+
     #[test]
     fn test_part_1() {
         let input = include_str!("../data/input-test.txt");
-        let result = input
-            .lines()
-            .map(|l| parse_line(l).unwrap())
-            .filter(|(l, r)| fully_contains(l, r))
-            .count();
-        assert_eq!(result, 2);
+        assert_eq!(evaluate_str::<SectionRange>(input).contained, 2);
     }
 
     #[test]
     fn test_part_2() {
         let input = include_str!("../data/input-test.txt");
-        let result = input
-            .lines()
-            .map(|l| parse_line(l).unwrap())
-            .filter(|(l, r)| !find_intersection(l, r).is_empty())
-            .count();
-        assert_eq!(result, 4);
+        assert_eq!(evaluate_str::<SectionRange>(input).overlapping, 4);
+    }
+
+    #[test]
+    fn test_boxes() -> Result<(), Box<dyn Error>> {
+        let input = include_str!("../data/boxes-test.txt");
+        let tally = evaluate_str::<SectionBox>(input);
+        assert_eq!((tally.contained, tally.overlapping), (2, 3));
+        let boxes: Vec<(SectionBox, SectionBox)> = parse_pairs(input, Reversed::Reject)?;
+        assert_eq!(shared_size(&boxes), 7);
+        assert_eq!(tally.shared, 7);
+
+        // Boxes one section tall behave like the ranges they are drawn from.
+        let ranges = pairs(include_str!("../data/input.txt"));
+        let flat: Vec<(SectionBox, SectionBox)> = ranges
+            .iter()
+            .map(|&(a, b)| {
+                let row = SectionRange::new(1, 1).unwrap();
+                (SectionBox::new(a, row), SectionBox::new(b, row))
+            })
+            .collect();
+        let (flat, ranges) = (tally_pairs(&flat), tally_pairs(&ranges));
+        assert_eq!(
+            (flat.contained, flat.overlapping),
+            (ranges.contained, ranges.overlapping)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_args() -> Result<(), Box<dyn Error>> {
+        assert!(matches!(
+            parse_args(std::iter::empty())?.mode,
+            Mode::Answers
+        ));
+        let args = ["--teams"].map(String::from);
+        assert!(matches!(parse_args(args.into_iter())?.mode, Mode::Teams));
+        assert!(parse_args(["--bogus"].map(String::from).into_iter()).is_err());
+
+        let args = ["--coverage", "--crowded", "3", "--bar"].map(String::from);
+        let options = parse_args(args.into_iter())?;
+        assert!(matches!(options.mode, Mode::Coverage));
+        assert_eq!(options.crowded, 3);
+        assert!(options.bar);
+        assert!(parse_args(["--crowded"].map(String::from).into_iter()).is_err());
+
+        let args = ["--render", "--lines", "3-5", "--width", "40"].map(String::from);
+        let options = parse_args(args.into_iter())?;
+        assert!(matches!(options.mode, Mode::Render));
+        assert_eq!(options.lines, SectionRange::new(3, 5));
+        assert_eq!(options.width, Some(40));
+
+        let args = ["--optimize"].map(String::from);
+        assert!(matches!(parse_args(args.into_iter())?.mode, Mode::Optimize));
+
+        let args = ["--query", "intersect", "3-7"].map(String::from);
+        assert!(matches!(
+            parse_args(args.into_iter())?.mode,
+            Mode::Query(Query::Intersect(r)) if r == SectionRange::new(3, 7).unwrap()
+        ));
+        let args = ["--query", "count", "x"].map(String::from);
+        assert!(parse_args(args.into_iter()).is_err());
+        let args = ["--query", "nearest", "4"].map(String::from);
+        assert!(parse_args(args.into_iter()).is_err());
+
+        let args = ["--relation", "met-by"].map(String::from);
+        assert!(matches!(
+            parse_args(args.into_iter())?.mode,
+            Mode::Relations(Some(Relation::MetBy))
+        ));
+        let args = ["--relations"].map(String::from);
+        assert!(matches!(
+            parse_args(args.into_iter())?.mode,
+            Mode::Relations(None)
+        ));
+
+        assert_eq!(parse_args(std::iter::empty())?.reversed, Reversed::Reject);
+        let args = ["--normalize", "--teams"].map(String::from);
+        assert_eq!(parse_args(args.into_iter())?.reversed, Reversed::Swap);
+
+        let options = parse_args(["--boxes"].map(String::from).into_iter())?;
+        assert!(matches!(options.mode, Mode::Answers));
+        assert!(options.boxes);
+        let args = ["--boxes", "--teams"].map(String::from);
+        assert!(parse_args(args.into_iter()).is_err());
+
+        let args = ["--boxes", "--stream", "--every", "1000"].map(String::from);
+        let options = parse_args(args.into_iter())?;
+        assert!(matches!(options.mode, Mode::Stream));
+        assert!(options.boxes);
+        assert_eq!(options.every, NonZeroUsize::new(1000));
+        let args = ["--stream", "--every", "0"].map(String::from);
+        assert!(parse_args(args.into_iter()).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_render_span() {
+        assert_eq!(render_span(2, 8, 60), SectionRange::new(1, 8));
+        assert_eq!(render_span(0, 8, 60), SectionRange::new(0, 8));
+        assert_eq!(render_span(2, 80, 60), SectionRange::new(2, 80));
+
+        // Large section IDs keep a column per section when their span fits.
+        let span = render_span(1_000_000, 1_000_020, 35).unwrap();
+        assert_eq!(span, SectionRange::new(1_000_000, 1_000_020).unwrap());
+        let canvas = Canvas::new(span, 35);
+        assert!(!canvas.is_scaled());
+        let first = SectionRange::new(1_000_000, 1_000_010).unwrap();
+        let second = SectionRange::new(1_000_005, 1_000_020).unwrap();
+        assert_eq!(canvas.draw(&first), "01234567890..........");
+        assert_eq!(canvas.draw(&second), ".....5678901234567890");
+        let overlap = first.intersection(&second).unwrap();
+        assert_eq!(canvas.highlight(&overlap), ".....######..........");
+
+        // And are spread over every column when it does not.
+        let span = render_span(1_000_000, 1_001_000, 50).unwrap();
+        let canvas = Canvas::new(span, 50);
+        assert_eq!(canvas.columns(), 48);
+        let low = SectionRange::new(1_000_000, 1_000_100).unwrap();
+        assert_eq!(canvas.draw(&low).matches('=').count(), 5);
+    }
+
+    #[test]
+    fn test_range_list() {
+        assert_eq!(range_list(&[]), "none");
+        let ranges = [
+            SectionRange::new(2, 4).unwrap(),
+            SectionRange::new(6, 6).unwrap(),
+        ];
+        assert_eq!(range_list(&ranges), "2-4,6-6");
+    }
+
+    #[test]
+    fn test_build_index() -> Result<(), Box<dyn Error>> {
+        let teams = parse_teams(include_str!("../data/input-test.txt"), Reversed::Reject)?;
+        let index = build_index(&teams);
+        assert_eq!(index.len(), 12);
+        assert_eq!(index.count(6), 8);
+
+        let lines: Vec<usize> = index.covering(6).iter().map(|a| a.line_number).collect();
+        assert_eq!(lines, vec![1, 3, 4, 4, 5, 5, 6, 6]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_relations_agree_with_parts() {
+        let pairs = pairs(include_str!("../data/input.txt"));
+        let mut contained = 0;
+        let mut overlapping = 0;
+        for (a, b) in &pairs {
+            let relation = Relation::between(a, b);
+            contained += usize::from(relation.is_containment());
+            overlapping += usize::from(relation.shares_sections());
+        }
+        let tally = tally_pairs(&pairs);
+        assert_eq!(
+            (contained, overlapping),
+            (tally.contained, tally.overlapping)
+        );
+    }
+
+    #[test]
+    fn test_input_is_valid() {
+        let input = include_str!("../data/input.txt");
+        assert!(validate_pairs::<SectionRange>(input, Reversed::Reject).is_empty());
+    }
+
+    #[test]
+    fn test_answers() {
+        let input = include_str!("../data/input.txt");
+        let tally = evaluate_str::<SectionRange>(input);
+        assert_eq!((tally.contained, tally.overlapping), (515, 883));
     }
 }
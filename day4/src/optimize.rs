@@ -0,0 +1,112 @@
+use crate::section_range::SectionRange;
+
+/// Reassigns an overlapping pair so that nobody cleans a section twice.
+///
+/// The sections the pair covers between them are split into two adjacent
+/// halves, the larger one going to whoever started first, so the sizes
+/// differ by at most one section. A pair that does not overlap already
+/// does no duplicated work and is returned as is, and a pair sharing a
+/// single section has to keep sharing it.
+pub fn rebalance(a: &SectionRange, b: &SectionRange) -> (SectionRange, SectionRange) {
+    if !a.overlaps(b) {
+        return (*a, *b);
+    }
+
+    let start = a.start().min(b.start());
+    let end = a.end().max(b.end());
+    let Some(union) = SectionRange::new(start, end) else {
+        return (*a, *b);
+    };
+    if union.length() == 1 {
+        return (*a, *b);
+    }
+
+    // Both halves fit in u32 since the union does.
+    let middle = (u64::from(start) + union.length().div_ceil(2) - 1) as u32;
+    let lower = SectionRange::new(start, middle).expect("the union has two sections");
+    let upper = SectionRange::new(middle + 1, end).expect("the union has two sections");
+
+    match b.start() < a.start() {
+        true => (upper, lower),
+        false => (lower, upper),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::region::shared_size;
+    use crate::section_range::range;
+
+    #[test]
+    fn test_rebalance() {
+        assert_eq!(
+            rebalance(&range(2, 8), &range(3, 7)),
+            (range(2, 5), range(6, 8))
+        );
+        assert_eq!(
+            rebalance(&range(6, 8), &range(2, 6)),
+            (range(6, 8), range(2, 5))
+        );
+        assert_eq!(
+            rebalance(&range(4, 6), &range(4, 6)),
+            (range(4, 5), range(6, 6))
+        );
+
+        // Nothing to gain.
+        assert_eq!(
+            rebalance(&range(2, 4), &range(6, 8)),
+            (range(2, 4), range(6, 8))
+        );
+        assert_eq!(
+            rebalance(&range(7, 7), &range(7, 7)),
+            (range(7, 7), range(7, 7))
+        );
+
+        assert_eq!(
+            rebalance(&range(0, u32::MAX), &range(5, 5)),
+            (range(0, u32::MAX / 2), range(u32::MAX / 2 + 1, u32::MAX))
+        );
+    }
+
+    #[test]
+    fn test_rebalance_small_ranges() {
+        let ranges: Vec<SectionRange> = (1..8)
+            .flat_map(|start| (start..8).map(move |end| range(start, end)))
+            .collect();
+        let covers = |a: &SectionRange, b: &SectionRange, section| {
+            a.contains(&range(section, section)) || b.contains(&range(section, section))
+        };
+
+        for a in &ranges {
+            for b in &ranges {
+                let (x, y) = rebalance(a, b);
+                for section in 1..8 {
+                    assert_eq!(covers(a, b, section), covers(&x, &y, section));
+                }
+
+                if !a.overlaps(b) {
+                    assert_eq!((x, y), (*a, *b));
+                    continue;
+                }
+                let overlap = x.intersection(&y).map_or(0, |r| r.length());
+                let single = a == b && a.length() == 1;
+                assert_eq!(overlap, u64::from(single), "{} {} -> {} {}", a, b, x, y);
+                assert!(x.length().abs_diff(y.length()) <= 1);
+            }
+        }
+    }
+
+    #[test]
+    fn test_rebalance_removes_overlap() {
+        let pairs = [
+            (range(2, 8), range(3, 7)),
+            (range(2, 4), range(6, 8)),
+            (range(5, 7), range(7, 9)),
+        ];
+        assert_eq!(shared_size(&pairs), 6);
+
+        let rebalanced: Vec<_> = pairs.iter().map(|(a, b)| rebalance(a, b)).collect();
+        assert_eq!(shared_size(&rebalanced), 0);
+    }
+}
@@ -0,0 +1,290 @@
+use std::error::Error;
+use std::fmt;
+use std::io;
+
+use crate::region::Region;
+use crate::section_box::SectionBox;
+use crate::section_range::SectionRange;
+use crate::team::Team;
+
+/// What to do with a range like `8-3` that ends before it starts.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Reversed {
+    Reject,
+    /// Reads it as `3-8`.
+    Swap,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum RangeError {
+    MissingSeparator { range: String },
+    InvalidBound { bound: String },
+    Reversed { start: u32, end: u32 },
+    NotABox { region: String },
+    RangeCount { expected: usize, found: usize },
+    Io { kind: io::ErrorKind },
+}
+
+impl fmt::Display for RangeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RangeError::MissingSeparator { range } => {
+                write!(f, "range {:?} has no '-'", range)
+            }
+            RangeError::InvalidBound { bound } => {
+                write!(f, "{:?} is not a section number", bound)
+            }
+            RangeError::Reversed { start, end } => {
+                write!(f, "range {}-{} ends before it starts", start, end)
+            }
+            RangeError::NotABox { region } => {
+                write!(f, "box {:?} is not two ranges joined by 'x'", region)
+            }
+            RangeError::RangeCount { expected, found } => {
+                write!(f, "expected {} ranges, found {}", expected, found)
+            }
+            RangeError::Io { kind } => write!(f, "{}", kind),
+        }
+    }
+}
+
+impl Error for RangeError {}
+
+/// A line that could not be read, numbered from 1.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ParseError {
+    pub line_number: usize,
+    pub error: RangeError,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line_number, self.error)
+    }
+}
+
+impl Error for ParseError {}
+
+pub fn parse_range(text: &str, reversed: Reversed) -> Result<SectionRange, RangeError> {
+    let (start, end) = text
+        .split_once('-')
+        .ok_or_else(|| RangeError::MissingSeparator {
+            range: text.to_owned(),
+        })?;
+    let bound = |b: &str| {
+        b.parse::<u32>().map_err(|_| RangeError::InvalidBound {
+            bound: b.to_owned(),
+        })
+    };
+    let (start, end) = (bound(start)?, bound(end)?);
+
+    match (SectionRange::new(start, end), reversed) {
+        (Some(range), _) => Ok(range),
+        (None, Reversed::Swap) => Ok(SectionRange::new(end, start).expect("end < start")),
+        (None, Reversed::Reject) => Err(RangeError::Reversed { start, end }),
+    }
+}
+
+/// Reads a box as its column range, an `x`, then its row range.
+pub fn parse_box(text: &str, reversed: Reversed) -> Result<SectionBox, RangeError> {
+    match text.split('x').collect::<Vec<_>>()[..] {
+        [x, y] => Ok(SectionBox::new(
+            parse_range(x, reversed)?,
+            parse_range(y, reversed)?,
+        )),
+        _ => Err(RangeError::NotABox {
+            region: text.to_owned(),
+        }),
+    }
+}
+
+/// Reads a line of comma separated ranges, reporting the first that is not
+/// a valid range.
+pub fn parse_team(line: &str, line_number: usize, reversed: Reversed) -> Result<Team, ParseError> {
+    line.split(',')
+        .map(|range| parse_range(range, reversed))
+        .collect::<Result<Vec<_>, _>>()
+        .map(Team::new)
+        .map_err(|error| ParseError { line_number, error })
+}
+
+/// Reads a line that has to hold exactly two ranges, or two boxes.
+pub fn parse_pair<R: Region>(
+    line: &str,
+    line_number: usize,
+    reversed: Reversed,
+) -> Result<(R, R), ParseError> {
+    let regions = line
+        .split(',')
+        .map(|text| R::parse(text, reversed))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|error| ParseError { line_number, error })?;
+    match regions[..] {
+        [a, b] => Ok((a, b)),
+        _ => Err(ParseError {
+            line_number,
+            error: RangeError::RangeCount {
+                expected: 2,
+                found: regions.len(),
+            },
+        }),
+    }
+}
+
+pub fn parse_teams(input: &str, reversed: Reversed) -> Result<Vec<Team>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| parse_team(line, i + 1, reversed))
+        .collect()
+}
+
+pub fn parse_pairs<R: Region>(input: &str, reversed: Reversed) -> Result<Vec<(R, R)>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| parse_pair(line, i + 1, reversed))
+        .collect()
+}
+
+/// Reports every line that is not a list of valid ranges.
+pub fn validate(input: &str, reversed: Reversed) -> Vec<ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .filter_map(|(i, line)| parse_team(line, i + 1, reversed).err())
+        .collect()
+}
+
+/// Reports every line that is not exactly two valid ranges, or boxes.
+pub fn validate_pairs<R: Region>(input: &str, reversed: Reversed) -> Vec<ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .filter_map(|(i, line)| parse_pair::<R>(line, i + 1, reversed).err())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::section_range::range;
+
+    #[test]
+    fn test_parse_range() {
+        assert_eq!(parse_range("2-4", Reversed::Reject), Ok(range(2, 4)));
+        assert_eq!(
+            parse_range("24", Reversed::Reject),
+            Err(RangeError::MissingSeparator {
+                range: "24".to_owned()
+            })
+        );
+        assert_eq!(
+            parse_range("2-x", Reversed::Reject),
+            Err(RangeError::InvalidBound {
+                bound: "x".to_owned()
+            })
+        );
+        assert_eq!(
+            parse_range("-2-4", Reversed::Reject),
+            Err(RangeError::InvalidBound {
+                bound: "".to_owned()
+            })
+        );
+        assert_eq!(
+            parse_range("8-3", Reversed::Reject),
+            Err(RangeError::Reversed { start: 8, end: 3 })
+        );
+        assert_eq!(parse_range("8-3", Reversed::Swap), Ok(range(3, 8)));
+    }
+
+    #[test]
+    fn test_parse_pair() {
+        assert_eq!(
+            parse_pair("2-4,6-8", 1, Reversed::Reject),
+            Ok((range(2, 4), range(6, 8)))
+        );
+        assert_eq!(
+            parse_pair::<SectionRange>("2-4", 3, Reversed::Reject),
+            Err(ParseError {
+                line_number: 3,
+                error: RangeError::RangeCount {
+                    expected: 2,
+                    found: 1
+                }
+            })
+        );
+        assert_eq!(
+            parse_pair::<SectionRange>("2-4,6-8,1-1", 4, Reversed::Reject)
+                .unwrap_err()
+                .to_string(),
+            "line 4: expected 2 ranges, found 3"
+        );
+        assert_eq!(
+            parse_pair::<SectionRange>("2-4;6-8", 5, Reversed::Reject)
+                .unwrap_err()
+                .to_string(),
+            "line 5: \"4;6-8\" is not a section number"
+        );
+    }
+
+    #[test]
+    fn test_parse_box() {
+        assert_eq!(
+            parse_box("2-4x1-3", Reversed::Reject),
+            Ok(SectionBox::new(range(2, 4), range(1, 3)))
+        );
+        assert_eq!(
+            parse_box("4-2x3-1", Reversed::Swap),
+            Ok(SectionBox::new(range(2, 4), range(1, 3)))
+        );
+        assert_eq!(
+            parse_box("2-4x3-1", Reversed::Reject),
+            Err(RangeError::Reversed { start: 3, end: 1 })
+        );
+        assert_eq!(
+            parse_pair::<SectionBox>("2-4x1-3,6-8", 2, Reversed::Reject)
+                .unwrap_err()
+                .to_string(),
+            "line 2: box \"6-8\" is not two ranges joined by 'x'"
+        );
+        assert_eq!(
+            parse_pair("2-4x1-3,6-8x2-2", 1, Reversed::Reject),
+            Ok((
+                SectionBox::new(range(2, 4), range(1, 3)),
+                SectionBox::new(range(6, 8), range(2, 2))
+            ))
+        );
+    }
+
+    #[test]
+    fn test_validate() {
+        let input = include_str!("../data/input-test.txt");
+        assert!(validate(input, Reversed::Reject).is_empty());
+
+        let input = "2-4,6-8\n8-3,1-2\n2-4,6\n\n";
+        let errors: Vec<String> = validate(input, Reversed::Reject)
+            .iter()
+            .map(|e| e.to_string())
+            .collect();
+        assert_eq!(
+            errors,
+            vec![
+                "line 2: range 8-3 ends before it starts",
+                "line 3: range \"6\" has no '-'",
+                "line 4: range \"\" has no '-'",
+            ]
+        );
+
+        let errors = validate(input, Reversed::Swap);
+        assert_eq!(errors.len(), 2);
+        let errors = validate_pairs::<SectionRange>("2-4,6-8,1-1\n2-4,6-8\n", Reversed::Reject);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].line_number, 1);
+        assert!(validate("2-4,6-8,1-1\n", Reversed::Reject).is_empty());
+        assert_eq!(
+            parse_teams("8-3,1-2\n", Reversed::Swap).map(|t| t[0].members()[0]),
+            Ok(range(3, 8))
+        );
+    }
+}
@@ -0,0 +1,113 @@
+use std::fmt;
+
+use crate::parse::{parse_box, parse_range, RangeError, Reversed};
+use crate::section_box::SectionBox;
+use crate::section_range::SectionRange;
+
+/// An assignment that pairs can be compared on, whatever its dimensions.
+pub trait Region: Copy + fmt::Display {
+    /// Whether every section of `other` is also in this region.
+    fn contains(&self, other: &Self) -> bool;
+
+    fn overlaps(&self, other: &Self) -> bool;
+
+    fn intersection(&self, other: &Self) -> Option<Self>;
+
+    /// The number of sections, which is an area for boxes.
+    fn size(&self) -> u128;
+
+    fn parse(text: &str, reversed: Reversed) -> Result<Self, RangeError>;
+}
+
+impl Region for SectionRange {
+    fn contains(&self, other: &Self) -> bool {
+        SectionRange::contains(self, other)
+    }
+
+    fn overlaps(&self, other: &Self) -> bool {
+        SectionRange::overlaps(self, other)
+    }
+
+    fn intersection(&self, other: &Self) -> Option<Self> {
+        SectionRange::intersection(self, other)
+    }
+
+    fn size(&self) -> u128 {
+        u128::from(self.length())
+    }
+
+    fn parse(text: &str, reversed: Reversed) -> Result<Self, RangeError> {
+        parse_range(text, reversed)
+    }
+}
+
+impl Region for SectionBox {
+    fn contains(&self, other: &Self) -> bool {
+        SectionBox::contains(self, other)
+    }
+
+    fn overlaps(&self, other: &Self) -> bool {
+        SectionBox::overlaps(self, other)
+    }
+
+    fn intersection(&self, other: &Self) -> Option<Self> {
+        SectionBox::intersection(self, other)
+    }
+
+    fn size(&self) -> u128 {
+        self.area()
+    }
+
+    fn parse(text: &str, reversed: Reversed) -> Result<Self, RangeError> {
+        parse_box(text, reversed)
+    }
+}
+
+/// Whether either region of the pair holds the other.
+pub fn fully_contains<R: Region>(a: &R, b: &R) -> bool {
+    a.contains(b) || b.contains(a)
+}
+
+/// The sections both regions of each pair cover, summed.
+pub fn shared_size<'a, R, I>(pairs: I) -> u128
+where
+    R: Region + 'a,
+    I: IntoIterator<Item = &'a (R, R)>,
+{
+    pairs
+        .into_iter()
+        .filter_map(|(a, b)| a.intersection(b))
+        .map(|r| r.size())
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::section_range::range;
+
+    #[test]
+    fn test_shared_size() {
+        let pairs = [
+            (range(2, 8), range(3, 7)),
+            (range(2, 4), range(6, 8)),
+            (range(5, 7), range(7, 9)),
+        ];
+        assert_eq!(shared_size(&pairs), 6);
+        assert!(fully_contains(&pairs[0].1, &pairs[0].0));
+
+        let boxes = [
+            (
+                SectionBox::new(range(2, 8), range(1, 4)),
+                SectionBox::new(range(3, 7), range(2, 2)),
+            ),
+            (
+                SectionBox::new(range(2, 4), range(1, 3)),
+                SectionBox::new(range(3, 5), range(3, 6)),
+            ),
+        ];
+        assert_eq!(shared_size(&boxes), 5 + 2);
+        assert!(fully_contains(&boxes[0].0, &boxes[0].1));
+        assert!(!fully_contains(&boxes[1].0, &boxes[1].1));
+    }
+}
@@ -0,0 +1,194 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::section_range::SectionRange;
+
+/// How one range sits relative to another, after Allen's interval algebra.
+///
+/// Sections are whole numbers, so a range meets the next one when it ends
+/// on the section just before it starts, rather than on the same point.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum Relation {
+    Before,
+    Meets,
+    Overlaps,
+    Starts,
+    During,
+    Finishes,
+    Equals,
+    FinishedBy,
+    Contains,
+    StartedBy,
+    OverlappedBy,
+    MetBy,
+    After,
+}
+
+impl Relation {
+    pub const ALL: [Relation; 13] = [
+        Relation::Before,
+        Relation::Meets,
+        Relation::Overlaps,
+        Relation::Starts,
+        Relation::During,
+        Relation::Finishes,
+        Relation::Equals,
+        Relation::FinishedBy,
+        Relation::Contains,
+        Relation::StartedBy,
+        Relation::OverlappedBy,
+        Relation::MetBy,
+        Relation::After,
+    ];
+
+    /// The relation of `a` to `b`.
+    pub fn between(a: &SectionRange, b: &SectionRange) -> Self {
+        match (a.start().cmp(&b.start()), a.end().cmp(&b.end())) {
+            (Ordering::Equal, Ordering::Equal) => Relation::Equals,
+            (Ordering::Equal, Ordering::Less) => Relation::Starts,
+            (Ordering::Equal, Ordering::Greater) => Relation::StartedBy,
+            (Ordering::Greater, Ordering::Equal) => Relation::Finishes,
+            (Ordering::Less, Ordering::Equal) => Relation::FinishedBy,
+            (Ordering::Greater, Ordering::Less) => Relation::During,
+            (Ordering::Less, Ordering::Greater) => Relation::Contains,
+            (Ordering::Less, Ordering::Less) => match u64::from(a.end()) + 1 {
+                next if next < u64::from(b.start()) => Relation::Before,
+                next if next == u64::from(b.start()) => Relation::Meets,
+                _ => Relation::Overlaps,
+            },
+            (Ordering::Greater, Ordering::Greater) => match u64::from(b.end()) + 1 {
+                next if next < u64::from(a.start()) => Relation::After,
+                next if next == u64::from(a.start()) => Relation::MetBy,
+                _ => Relation::OverlappedBy,
+            },
+        }
+    }
+
+    /// The relation of `b` to `a`.
+    pub fn inverse(&self) -> Self {
+        Relation::ALL[Relation::ALL.len() - 1 - *self as usize]
+    }
+
+    /// Whether one of the ranges holds the other, as `fully_contains` tells.
+    pub fn is_containment(&self) -> bool {
+        matches!(
+            self,
+            Relation::Starts
+                | Relation::During
+                | Relation::Finishes
+                | Relation::Equals
+                | Relation::FinishedBy
+                | Relation::Contains
+                | Relation::StartedBy
+        )
+    }
+
+    /// Whether the ranges have a section in common, as `overlaps` tells.
+    pub fn shares_sections(&self) -> bool {
+        !matches!(
+            self,
+            Relation::Before | Relation::Meets | Relation::MetBy | Relation::After
+        )
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Relation::Before => "before",
+            Relation::Meets => "meets",
+            Relation::Overlaps => "overlaps",
+            Relation::Starts => "starts",
+            Relation::During => "during",
+            Relation::Finishes => "finishes",
+            Relation::Equals => "equals",
+            Relation::FinishedBy => "finished-by",
+            Relation::Contains => "contains",
+            Relation::StartedBy => "started-by",
+            Relation::OverlappedBy => "overlapped-by",
+            Relation::MetBy => "met-by",
+            Relation::After => "after",
+        }
+    }
+}
+
+impl fmt::Display for Relation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(self.name())
+    }
+}
+
+impl FromStr for Relation {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Relation::ALL
+            .into_iter()
+            .find(|r| r.name() == s)
+            .ok_or_else(|| format!("unknown relation {}", s))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::section_range::range;
+
+    #[test]
+    fn test_between() {
+        let cases = [
+            ((2, 4), (6, 8), Relation::Before),
+            ((2, 3), (4, 5), Relation::Meets),
+            ((5, 7), (7, 9), Relation::Overlaps),
+            ((2, 3), (2, 8), Relation::Starts),
+            ((3, 7), (2, 8), Relation::During),
+            ((6, 6), (4, 6), Relation::Finishes),
+            ((4, 6), (4, 6), Relation::Equals),
+            ((2, 8), (3, 7), Relation::Contains),
+            ((6, 8), (2, 4), Relation::After),
+        ];
+        for ((a1, a2), (b1, b2), relation) in cases {
+            let (a, b) = (range(a1, a2), range(b1, b2));
+            assert_eq!(Relation::between(&a, &b), relation, "{} {}", a, b);
+            assert_eq!(Relation::between(&b, &a), relation.inverse());
+        }
+
+        let (a, b) = (range(0, u32::MAX - 1), range(u32::MAX, u32::MAX));
+        assert_eq!(Relation::between(&a, &b), Relation::Meets);
+    }
+
+    #[test]
+    fn test_matches_range_operations() {
+        let ranges: Vec<SectionRange> = (1..9)
+            .flat_map(|start| (start..9).map(move |end| range(start, end)))
+            .collect();
+
+        let mut seen = Vec::new();
+        for a in &ranges {
+            for b in &ranges {
+                let relation = Relation::between(a, b);
+                assert_eq!(
+                    relation.is_containment(),
+                    a.contains(b) || b.contains(a),
+                    "{} {}",
+                    a,
+                    b
+                );
+                assert_eq!(relation.shares_sections(), a.overlaps(b), "{} {}", a, b);
+                assert_eq!(Relation::between(b, a), relation.inverse());
+                if !seen.contains(&relation) {
+                    seen.push(relation);
+                }
+            }
+        }
+        assert_eq!(seen.len(), Relation::ALL.len());
+    }
+
+    #[test]
+    fn test_names() {
+        for relation in Relation::ALL {
+            assert_eq!(relation.to_string().parse(), Ok(relation));
+        }
+        assert_eq!(Relation::OverlappedBy.to_string(), "overlapped-by");
+        assert!("inside".parse::<Relation>().is_err());
+    }
+}
@@ -0,0 +1,101 @@
+use crate::section_range::SectionRange;
+
+/// Draws ranges as rows of columns the way the puzzle does, as in
+/// `.234.....`, squeezing several sections into each column when the span
+/// is wider than the canvas.
+#[derive(Debug)]
+pub struct Canvas {
+    span: SectionRange,
+    per_column: u64,
+    columns: usize,
+}
+
+impl Canvas {
+    pub fn new(span: SectionRange, width: usize) -> Self {
+        let per_column = span.length().div_ceil(width.max(1) as u64);
+        Canvas {
+            span,
+            per_column,
+            columns: span.length().div_ceil(per_column) as usize,
+        }
+    }
+
+    pub fn columns(&self) -> usize {
+        self.columns
+    }
+
+    /// Whether a column holds more than one section.
+    pub fn is_scaled(&self) -> bool {
+        self.per_column > 1
+    }
+
+    fn column(&self, c: usize) -> SectionRange {
+        let start = u64::from(self.span.start()) + c as u64 * self.per_column;
+        let end = (start + self.per_column - 1).min(u64::from(self.span.end()));
+        SectionRange::new(start as u32, end as u32).expect("columns are within the span")
+    }
+
+    /// Shows the last digit of each section in the range, or `=` for the
+    /// columns it touches once the canvas is scaled.
+    pub fn draw(&self, range: &SectionRange) -> String {
+        (0..self.columns)
+            .map(|c| {
+                let column = self.column(c);
+                match (self.is_scaled(), range.overlaps(&column)) {
+                    (_, false) => '.',
+                    (true, true) => '=',
+                    (false, true) => char::from(b'0' + (column.start() % 10) as u8),
+                }
+            })
+            .collect()
+    }
+
+    /// Marks the columns the range touches with `#`.
+    pub fn highlight(&self, range: &SectionRange) -> String {
+        (0..self.columns)
+            .map(|c| match range.overlaps(&self.column(c)) {
+                true => '#',
+                false => '.',
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::section_range::range;
+
+    #[test]
+    fn test_draw_puzzle_pairs() {
+        let canvas = Canvas::new(range(1, 9), 80);
+        assert!(!canvas.is_scaled());
+        assert_eq!(canvas.draw(&range(2, 4)), ".234.....");
+        assert_eq!(canvas.draw(&range(6, 8)), ".....678.");
+        assert_eq!(canvas.draw(&range(2, 8)), ".2345678.");
+        assert_eq!(canvas.draw(&range(3, 7)), "..34567..");
+        assert_eq!(canvas.highlight(&range(3, 7)), "..#####..");
+    }
+
+    #[test]
+    fn test_draw_scaled() {
+        let canvas = Canvas::new(range(1, 100), 10);
+        assert!(canvas.is_scaled());
+        assert_eq!(canvas.columns(), 10);
+        assert_eq!(canvas.draw(&range(15, 35)), ".===......");
+        assert_eq!(canvas.highlight(&range(100, 100)), ".........#");
+
+        let canvas = Canvas::new(range(0, u32::MAX), 64);
+        assert_eq!(canvas.columns(), 64);
+        assert_eq!(canvas.draw(&range(u32::MAX, u32::MAX)).pop(), Some('='));
+    }
+
+    #[test]
+    fn test_draw_uneven_scale() {
+        // Three sections a column leaves the last column one section wide.
+        let canvas = Canvas::new(range(1, 10), 4);
+        assert_eq!(canvas.columns(), 4);
+        assert_eq!(canvas.draw(&range(10, 10)), "...=");
+        assert_eq!(canvas.draw(&range(3, 4)), "==..");
+    }
+}
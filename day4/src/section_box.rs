@@ -0,0 +1,103 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::parse::{parse_box, RangeError, Reversed};
+use crate::section_range::SectionRange;
+
+/// A rectangle of sections on the camp's grid, written `2-4x1-3` with the
+/// columns first.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct SectionBox {
+    x: SectionRange,
+    y: SectionRange,
+}
+
+impl SectionBox {
+    pub fn new(x: SectionRange, y: SectionRange) -> Self {
+        SectionBox { x, y }
+    }
+
+    pub fn x(&self) -> SectionRange {
+        self.x
+    }
+
+    pub fn y(&self) -> SectionRange {
+        self.y
+    }
+
+    /// The number of sections, which needs more than 64 bits for the whole
+    /// grid.
+    pub fn area(&self) -> u128 {
+        u128::from(self.x.length()) * u128::from(self.y.length())
+    }
+
+    pub fn contains(&self, other: &SectionBox) -> bool {
+        self.x.contains(&other.x) && self.y.contains(&other.y)
+    }
+
+    pub fn overlaps(&self, other: &SectionBox) -> bool {
+        self.x.overlaps(&other.x) && self.y.overlaps(&other.y)
+    }
+
+    pub fn intersection(&self, other: &SectionBox) -> Option<SectionBox> {
+        Some(SectionBox::new(
+            self.x.intersection(&other.x)?,
+            self.y.intersection(&other.y)?,
+        ))
+    }
+}
+
+impl fmt::Display for SectionBox {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}x{}", self.x, self.y)
+    }
+}
+
+impl FromStr for SectionBox {
+    type Err = RangeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_box(s, Reversed::Reject)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::section_range::range;
+    use std::error::Error;
+
+    #[test]
+    fn test_parse() -> Result<(), Box<dyn Error>> {
+        let b: SectionBox = "2-4x1-3".parse()?;
+        assert_eq!(b, SectionBox::new(range(2, 4), range(1, 3)));
+        assert_eq!(b.to_string(), "2-4x1-3");
+        assert!("2-4".parse::<SectionBox>().is_err());
+        assert!("2-4x1-3x5-6".parse::<SectionBox>().is_err());
+        assert!("2-4x3-1".parse::<SectionBox>().is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_operations() -> Result<(), Box<dyn Error>> {
+        let (a, b): (SectionBox, SectionBox) = ("2-8x1-4".parse()?, "3-7x2-2".parse()?);
+        assert!(a.contains(&b));
+        assert!(!b.contains(&a));
+        assert_eq!(a.intersection(&b), Some(b));
+        assert_eq!(a.area(), 28);
+
+        // Overlapping columns alone are not enough.
+        let (a, b): (SectionBox, SectionBox) = ("2-4x1-3".parse()?, "3-5x4-6".parse()?);
+        assert!(!a.overlaps(&b));
+        assert_eq!(a.intersection(&b), None);
+
+        let (a, b): (SectionBox, SectionBox) = ("2-4x1-3".parse()?, "4-6x3-5".parse()?);
+        assert!(a.overlaps(&b));
+        assert!(!a.contains(&b));
+        assert_eq!(a.intersection(&b), Some("4-4x3-3".parse()?));
+
+        let grid: SectionBox = "0-4294967295x0-4294967295".parse()?;
+        assert_eq!(grid.area(), 1 << 64);
+        Ok(())
+    }
+}
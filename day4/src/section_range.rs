@@ -0,0 +1,111 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::parse::{parse_range, RangeError, Reversed};
+
+/// The sections `start..=end` assigned to an elf, never empty.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct SectionRange {
+    start: u32,
+    end: u32,
+}
+
+impl SectionRange {
+    pub fn new(start: u32, end: u32) -> Option<Self> {
+        match start <= end {
+            true => Some(SectionRange { start, end }),
+            false => None,
+        }
+    }
+
+    pub fn start(&self) -> u32 {
+        self.start
+    }
+
+    pub fn end(&self) -> u32 {
+        self.end
+    }
+
+    /// The number of sections, which needs more than 32 bits for `0-4294967295`.
+    pub fn length(&self) -> u64 {
+        u64::from(self.end - self.start) + 1
+    }
+
+    /// Whether every section of `other` is also in this range.
+    pub fn contains(&self, other: &SectionRange) -> bool {
+        self.start <= other.start && other.end <= self.end
+    }
+
+    pub fn overlaps(&self, other: &SectionRange) -> bool {
+        self.start <= other.end && other.start <= self.end
+    }
+
+    pub fn intersection(&self, other: &SectionRange) -> Option<SectionRange> {
+        SectionRange::new(self.start.max(other.start), self.end.min(other.end))
+    }
+}
+
+impl fmt::Display for SectionRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{}", self.start, self.end)
+    }
+}
+
+impl FromStr for SectionRange {
+    type Err = RangeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_range(s, Reversed::Reject)
+    }
+}
+
+/// Builds a range for tests, panicking when it is reversed.
+#[cfg(test)]
+pub(crate) fn range(start: u32, end: u32) -> SectionRange {
+    SectionRange::new(start, end).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::error::Error;
+
+    #[test]
+    fn test_parse() -> Result<(), Box<dyn Error>> {
+        assert_eq!("2-4".parse::<SectionRange>()?, range(2, 4));
+        assert_eq!("7-7".parse::<SectionRange>()?, range(7, 7));
+        assert_eq!(range(2, 4).to_string(), "2-4");
+        assert!("4-2".parse::<SectionRange>().is_err());
+        assert!("24".parse::<SectionRange>().is_err());
+        assert!("a-4".parse::<SectionRange>().is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_operations() {
+        let (a, b) = (range(2, 8), range(3, 7));
+        assert!(a.contains(&b));
+        assert!(!b.contains(&a));
+        assert!(a.overlaps(&b));
+        assert_eq!(a.intersection(&b), Some(b));
+        assert_eq!(a.length(), 7);
+
+        let (a, b) = (range(2, 4), range(6, 8));
+        assert!(!a.overlaps(&b));
+        assert_eq!(a.intersection(&b), None);
+
+        let (a, b) = (range(5, 7), range(7, 9));
+        assert!(a.overlaps(&b));
+        assert_eq!(a.intersection(&b), Some(range(7, 7)));
+    }
+
+    #[test]
+    fn test_huge_ranges() -> Result<(), Box<dyn Error>> {
+        let a: SectionRange = "1-4000000000".parse()?;
+        let b: SectionRange = "0-4294967295".parse()?;
+        assert!(b.contains(&a));
+        assert_eq!(b.length(), 1 << 32);
+        assert_eq!(a.intersection(&b), Some(a));
+        Ok(())
+    }
+}
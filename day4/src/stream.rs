@@ -0,0 +1,147 @@
+use std::fmt;
+use std::io::BufRead;
+use std::num::NonZeroUsize;
+
+use crate::parse::{parse_pair, ParseError, RangeError, Reversed};
+use crate::region::{fully_contains, Region};
+
+/// Running counts of the pairs read so far.
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq)]
+pub struct Tally {
+    pub pairs: usize,
+    /// Pairs where one region holds the other, by `fully_contains`.
+    pub contained: usize,
+    /// Pairs sharing at least one section, by `overlaps`.
+    pub overlapping: usize,
+    /// The sections both regions of each pair cover, as `shared_size` sums.
+    pub shared: u128,
+}
+
+impl Tally {
+    pub fn add<R: Region>(&mut self, a: &R, b: &R) {
+        self.pairs += 1;
+        self.contained += usize::from(fully_contains(a, b));
+        self.overlapping += usize::from(a.overlaps(b));
+        self.shared += a.intersection(b).map_or(0, |r| r.size());
+    }
+}
+
+impl fmt::Display for Tally {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} pairs, {} contained, {} overlapping",
+            self.pairs, self.contained, self.overlapping
+        )
+    }
+}
+
+/// Counts both parts in one pass over the reader, without keeping the pairs.
+///
+/// With `every`, `progress` sees the running tally after each that many
+/// pairs. Reading stops at the first line that is not a valid pair.
+pub fn evaluate<R, B, F>(
+    reader: B,
+    reversed: Reversed,
+    every: Option<NonZeroUsize>,
+    mut progress: F,
+) -> Result<Tally, ParseError>
+where
+    R: Region,
+    B: BufRead,
+    F: FnMut(&Tally),
+{
+    let mut tally = Tally::default();
+    for (i, line) in reader.lines().enumerate() {
+        let line = line.map_err(|e| ParseError {
+            line_number: i + 1,
+            error: RangeError::Io { kind: e.kind() },
+        })?;
+        let (a, b): (R, R) = parse_pair(&line, i + 1, reversed)?;
+        tally.add(&a, &b);
+
+        if every.is_some_and(|n| tally.pairs % n == 0) {
+            progress(&tally);
+        }
+    }
+
+    Ok(tally)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::section_box::SectionBox;
+    use crate::section_range::SectionRange;
+    use std::io;
+
+    #[test]
+    fn test_evaluate() -> Result<(), ParseError> {
+        let input = include_str!("../data/input-test.txt");
+        let mut seen = Vec::new();
+        let tally = evaluate::<SectionRange, _, _>(
+            input.as_bytes(),
+            Reversed::Reject,
+            NonZeroUsize::new(2),
+            |t| seen.push(*t),
+        )?;
+        assert_eq!(
+            tally,
+            Tally {
+                pairs: 6,
+                contained: 2,
+                overlapping: 4,
+                shared: 10,
+            }
+        );
+        assert_eq!(
+            seen.iter().map(|t| t.to_string()).collect::<Vec<_>>(),
+            vec![
+                "2 pairs, 0 contained, 0 overlapping",
+                "4 pairs, 1 contained, 2 overlapping",
+                "6 pairs, 2 contained, 4 overlapping",
+            ]
+        );
+
+        let input = include_str!("../data/boxes-test.txt");
+        let tally = evaluate::<SectionBox, _, _>(input.as_bytes(), Reversed::Reject, None, |_| {})?;
+        assert_eq!(
+            (tally.contained, tally.overlapping, tally.shared),
+            (2, 3, 7)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_evaluate_errors() {
+        let error = evaluate::<SectionRange, _, _>(
+            &b"2-4,6-8\n8-3,1-2\n"[..],
+            Reversed::Reject,
+            None,
+            |_| {},
+        )
+        .unwrap_err();
+        assert_eq!(error.to_string(), "line 2: range 8-3 ends before it starts");
+
+        let error = evaluate::<SectionRange, _, _>(
+            &b"2-4,6-8\n\xff-1,1-2\n"[..],
+            Reversed::Reject,
+            None,
+            |_| {},
+        )
+        .unwrap_err();
+        assert_eq!(
+            error,
+            ParseError {
+                line_number: 2,
+                error: RangeError::Io {
+                    kind: io::ErrorKind::InvalidData
+                }
+            }
+        );
+
+        let tally =
+            evaluate::<SectionRange, _, _>(&b"8-3,4-5\r\n"[..], Reversed::Swap, None, |_| {});
+        assert_eq!(tally.map(|t| t.contained), Ok(1));
+    }
+}
@@ -0,0 +1,118 @@
+use std::str::FromStr;
+
+use crate::parse::RangeError;
+use crate::section_range::SectionRange;
+
+/// The ranges assigned to a team of elves on one line, in line order.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Team {
+    members: Vec<SectionRange>,
+}
+
+impl Team {
+    pub fn new(members: Vec<SectionRange>) -> Self {
+        Team { members }
+    }
+
+    pub fn members(&self) -> &[SectionRange] {
+        &self.members
+    }
+
+    /// The indices of members whose whole range another member also cleans.
+    ///
+    /// Of several members with the same range, all but the first are
+    /// redundant, so dropping every redundant member keeps the team's
+    /// coverage.
+    pub fn redundant(&self) -> Vec<usize> {
+        (0..self.members.len())
+            .filter(|&i| {
+                let member = &self.members[i];
+                self.members.iter().enumerate().any(|(j, other)| {
+                    j != i && other.contains(member) && (other != member || j < i)
+                })
+            })
+            .collect()
+    }
+
+    /// The number of sections each pair of members both clean, with each
+    /// member's own length on the diagonal.
+    pub fn overlap_matrix(&self) -> Vec<Vec<u64>> {
+        self.members
+            .iter()
+            .map(|a| {
+                self.members
+                    .iter()
+                    .map(|b| a.intersection(b).map_or(0, |r| r.length()))
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// The sections every member cleans, if there are any.
+    pub fn common(&self) -> Option<SectionRange> {
+        let (first, rest) = self.members.split_first()?;
+        rest.iter().try_fold(*first, |acc, r| acc.intersection(r))
+    }
+}
+
+impl FromStr for Team {
+    type Err = RangeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let members = s
+            .split(',')
+            .map(str::parse)
+            .collect::<Result<Vec<SectionRange>, _>>()?;
+        Ok(Team::new(members))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::error::Error;
+
+    #[test]
+    fn test_parse() -> Result<(), Box<dyn Error>> {
+        let team: Team = "2-4,6-8,3-5".parse()?;
+        assert_eq!(team.members().len(), 3);
+        assert_eq!(team.members()[2], SectionRange::new(3, 5).unwrap());
+
+        assert_eq!("7-7".parse::<Team>()?.members().len(), 1);
+        assert!("2-4,".parse::<Team>().is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_redundant() -> Result<(), Box<dyn Error>> {
+        let team: Team = "2-8,3-7,6-9,4-4".parse()?;
+        assert_eq!(team.redundant(), vec![1, 3]);
+
+        let team: Team = "2-4,6-8,2-4".parse()?;
+        assert_eq!(team.redundant(), vec![2]);
+
+        let team: Team = "1-3,4-6".parse()?;
+        assert!(team.redundant().is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_overlap_matrix() -> Result<(), Box<dyn Error>> {
+        let team: Team = "2-4,6-8,3-6".parse()?;
+        assert_eq!(
+            team.overlap_matrix(),
+            vec![vec![3, 0, 2], vec![0, 3, 1], vec![2, 1, 4]]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_common() -> Result<(), Box<dyn Error>> {
+        let team: Team = "2-8,3-7,6-9".parse()?;
+        assert_eq!(team.common(), SectionRange::new(6, 7));
+
+        let team: Team = "2-4,6-8,3-6".parse()?;
+        assert_eq!(team.common(), None);
+        Ok(())
+    }
+}